@@ -0,0 +1,203 @@
+use graph_core::Graph;
+
+use crate::graph::UWGraph;
+
+/// Single points of failure of an undirected graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Biconnectivity {
+    /// nodes whose removal disconnects their component, ascending
+    pub articulation_points: Vec<usize>,
+    /// edges whose removal disconnects their component as `(smaller id, larger id)`, ascending;
+    /// parallel edges are never bridges
+    pub bridges: Vec<(usize, usize)>,
+    /// node ids of every maximal biconnected subgraph, each ascending and the list sorted;
+    /// isolated nodes belong to none
+    pub components: Vec<Vec<usize>>,
+}
+
+impl UWGraph {
+    pub fn articulation_points(&self) -> Vec<usize> {
+        self.biconnectivity().articulation_points
+    }
+
+    pub fn bridges(&self) -> Vec<(usize, usize)> {
+        self.biconnectivity().bridges
+    }
+
+    pub fn biconnected_components(&self) -> Vec<Vec<usize>> {
+        self.biconnectivity().components
+    }
+
+    /// Tarjan's low-link search with explicit stacks, all three results in one pass
+    pub fn biconnectivity(&self) -> Biconnectivity {
+        let node_count = self.node_count();
+        // discovery time from 1, 0 for unvisited nodes
+        let mut discovered = vec![0_usize; node_count];
+        // earliest discovery time reachable through the node's subtree and one back edge
+        let mut low = vec![0_usize; node_count];
+        let mut is_articulation_point = vec![false; node_count];
+        let mut bridges = Vec::new();
+        let mut components = Vec::new();
+        // tree and back edges of the components still being built
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut time = 0;
+
+        for root in 1..=node_count {
+            if discovered[root - 1] != 0 {
+                continue;
+            }
+            time += 1;
+            discovered[root - 1] = time;
+            low[root - 1] = time;
+            let mut root_children = 0;
+            // node, its parent (0 for the root), whether the edge to the parent was skipped, neighbours
+            let mut stack = vec![(root, 0_usize, false, self.neighbours(root))];
+            while let Some((node_id, parent, skipped_parent, neighbours)) = stack.last_mut() {
+                let node_id = *node_id;
+                match neighbours.next() {
+                    Some(adjacent_node_id) => {
+                        if adjacent_node_id == node_id {
+                            continue;
+                        }
+                        // the tree edge is listed at both ends, a parallel edge is a back edge
+                        if adjacent_node_id == *parent && !*skipped_parent {
+                            *skipped_parent = true;
+                            continue;
+                        }
+                        if discovered[adjacent_node_id - 1] == 0 {
+                            time += 1;
+                            discovered[adjacent_node_id - 1] = time;
+                            low[adjacent_node_id - 1] = time;
+                            edges.push((node_id, adjacent_node_id));
+                            if node_id == root {
+                                root_children += 1;
+                            }
+                            stack.push((adjacent_node_id, node_id, false, self.neighbours(adjacent_node_id)));
+                        } else if discovered[adjacent_node_id - 1] < discovered[node_id - 1] {
+                            low[node_id - 1] = low[node_id - 1].min(discovered[adjacent_node_id - 1]);
+                            edges.push((node_id, adjacent_node_id));
+                        }
+                    }
+                    None => {
+                        let parent = *parent;
+                        stack.pop();
+                        if parent == 0 {
+                            continue;
+                        }
+                        low[parent - 1] = low[parent - 1].min(low[node_id - 1]);
+                        if low[node_id - 1] > discovered[parent - 1] {
+                            bridges.push((parent.min(node_id), parent.max(node_id)));
+                        }
+                        if low[node_id - 1] >= discovered[parent - 1] {
+                            is_articulation_point[parent - 1] |= parent != root;
+                            components.push(pop_component(&mut edges, (parent, node_id)));
+                        }
+                    }
+                }
+            }
+            is_articulation_point[root - 1] = root_children > 1;
+        }
+
+        bridges.sort_unstable();
+        components.sort_unstable();
+        let articulation_points = (1..=node_count)
+            .filter(|node_id| is_articulation_point[node_id - 1])
+            .collect();
+        Biconnectivity { articulation_points, bridges, components }
+    }
+}
+
+// pops edges down to the tree edge that closed the component
+fn pop_component(edges: &mut Vec<(usize, usize)>, tree_edge: (usize, usize)) -> Vec<usize> {
+    let mut nodes = Vec::new();
+    while let Some(edge) = edges.pop() {
+        nodes.push(edge.0);
+        nodes.push(edge.1);
+        if edge == tree_edge {
+            break;
+        }
+    }
+    nodes.sort_unstable();
+    nodes.dedup();
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::XorShift64;
+
+    use crate::graph::UWGraph;
+
+    fn unit_weights(edges: &[(usize, usize)]) -> Vec<(usize, usize, usize)> {
+        edges.iter().map(|(from, to)| (*from, *to, 1)).collect()
+    }
+
+    #[test]
+    fn finds_cut_vertices_bridges_and_blocks() {
+        // triangle 1-2-3, bridge 3-4, square 4-5-6-7, pendant 8 on 7, isolated 9
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (6, 7), (7, 4), (7, 8)];
+        let graph = UWGraph::from_edges(9, &unit_weights(&edges));
+        let biconnectivity = graph.biconnectivity();
+
+        assert_eq!(biconnectivity.articulation_points, vec![3, 4, 7]);
+        assert_eq!(biconnectivity.bridges, vec![(3, 4), (7, 8)]);
+        assert_eq!(biconnectivity.components, vec![vec![1, 2, 3], vec![3, 4], vec![4, 5, 6, 7], vec![7, 8]]);
+    }
+
+    #[test]
+    fn parallel_edges_are_not_bridges() {
+        let graph = UWGraph::from_edges(3, &unit_weights(&[(1, 2), (1, 2), (2, 3), (3, 3)]));
+
+        assert_eq!(graph.bridges(), vec![(2, 3)]);
+        assert_eq!(graph.articulation_points(), vec![2]);
+        assert_eq!(graph.biconnected_components(), vec![vec![1, 2], vec![2, 3]]);
+    }
+
+    #[test]
+    fn long_path_does_not_overflow() {
+        let edges = (1..200_000).map(|node_id| (node_id, node_id + 1)).collect::<Vec<_>>();
+        let graph = UWGraph::from_edges(200_000, &unit_weights(&edges));
+
+        assert_eq!(graph.articulation_points().len(), 199_998);
+        assert_eq!(graph.bridges().len(), 199_999);
+    }
+
+    #[test]
+    fn agrees_with_removal() {
+        let mut rng = XorShift64::new(11);
+        for _ in 0..100 {
+            let node_count = 1 + rng.below(12);
+            let edges = (0..rng.below(2 * node_count))
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count)))
+                .collect::<Vec<(usize, usize)>>();
+            let graph = UWGraph::from_edges(node_count, &unit_weights(&edges));
+            let biconnectivity = graph.biconnectivity();
+            let count = graph.connected_components().count();
+
+            for node_id in 1..=node_count {
+                let remaining = edges.iter().copied()
+                    .filter(|(from, to)| *from != node_id && *to != node_id)
+                    .collect::<Vec<_>>();
+                // the removed node stays behind as an isolated one
+                let is_cut = UWGraph::from_edges(node_count, &unit_weights(&remaining))
+                    .connected_components().count() > count + 1;
+                assert_eq!(biconnectivity.articulation_points.contains(&node_id), is_cut);
+            }
+            for index in 0..edges.len() {
+                let (from, to) = edges[index];
+                let mut remaining = edges.clone();
+                remaining.remove(index);
+                let is_bridge = UWGraph::from_edges(node_count, &unit_weights(&remaining))
+                    .connected_components().count() > count;
+                assert_eq!(biconnectivity.bridges.contains(&(from.min(to), from.max(to))), is_bridge);
+            }
+            // blocks share at most one node, and every edge lies in exactly one block
+            for (from, to) in edges.iter().filter(|(from, to)| from != to) {
+                let blocks = biconnectivity.components.iter()
+                    .filter(|block| block.contains(from) && block.contains(to))
+                    .count();
+                assert_eq!(blocks, 1);
+            }
+        }
+    }
+}
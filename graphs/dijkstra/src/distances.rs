@@ -0,0 +1,186 @@
+use graph_core::{Graph, shortest_paths, traversal, WeightedGraph};
+
+use crate::{graph::{DijkstraScore, UWGraph}, heap::Heap};
+
+// largest edge weight for which Dial's buckets beat the heap
+const DIAL_MAX_WEIGHT: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortestPathAlgorithm {
+    /// every edge has the same weight
+    BreadthFirst,
+    /// weights are 0 or 1
+    ZeroOneBreadthFirst,
+    /// small integer weights, bucket queue
+    Dial,
+    /// anything else, [`Heap`] based
+    Dijkstra,
+}
+
+impl UWGraph {
+    /// The fastest algorithm that handles every edge weight of the graph
+    pub fn shortest_path_algorithm(&self) -> ShortestPathAlgorithm {
+        let mut weights = (1..=self.node_count())
+            .flat_map(|node_id| self.weighted_neighbours(node_id).map(|(_, weight)| weight));
+        let Some(first) = weights.next() else {
+            return ShortestPathAlgorithm::BreadthFirst;
+        };
+        let (mut min_weight, mut max_weight) = (first, first);
+        for weight in weights {
+            min_weight = min_weight.min(weight);
+            max_weight = max_weight.max(weight);
+        }
+        if min_weight == max_weight {
+            ShortestPathAlgorithm::BreadthFirst
+        } else if max_weight <= 1 {
+            ShortestPathAlgorithm::ZeroOneBreadthFirst
+        } else if max_weight <= DIAL_MAX_WEIGHT {
+            ShortestPathAlgorithm::Dial
+        } else {
+            ShortestPathAlgorithm::Dijkstra
+        }
+    }
+
+    /// Shortest path length from `source` to every node, indexed by node_id - 1;
+    /// None for unreachable nodes
+    pub fn distances_from(&self, source: usize) -> Vec<Option<usize>> {
+        self.distances_with(source, self.shortest_path_algorithm())
+    }
+
+    /// Same as [`UWGraph::distances_from`] with a chosen algorithm,
+    /// which panics on weights it can't handle
+    pub fn distances_with(&self, source: usize, algorithm: ShortestPathAlgorithm) -> Vec<Option<usize>> {
+        match algorithm {
+            ShortestPathAlgorithm::BreadthFirst => {
+                let weight = (1..=self.node_count())
+                    .find_map(|node_id| self.weighted_neighbours(node_id).next())
+                    .map_or(0, |(_, weight)| weight);
+                traversal::breadth_first_distances(self, source)
+                    .into_iter()
+                    .map(|edge_count| edge_count.map(|edge_count| edge_count * weight))
+                    .collect()
+            }
+            ShortestPathAlgorithm::ZeroOneBreadthFirst => shortest_paths::zero_one_breadth_first(self, source),
+            ShortestPathAlgorithm::Dial => {
+                let max_weight = (1..=self.node_count())
+                    .flat_map(|node_id| self.weighted_neighbours(node_id).map(|(_, weight)| weight))
+                    .max()
+                    .unwrap_or(0);
+                shortest_paths::dial(self, source, max_weight)
+            }
+            ShortestPathAlgorithm::Dijkstra => self.dijkstra_distances(source),
+        }
+    }
+
+    fn dijkstra_distances(&self, source: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.node_count()];
+        let mut heap: Heap<DijkstraScore> = Heap::default();
+        heap.insert(DijkstraScore::from(source, 0));
+        while let Some(round_winner) = heap.extract_min() {
+            let distance = round_winner.score.expect("Only reached nodes get into the heap");
+            distances[round_winner.node_num - 1] = Some(distance);
+            for (adjacent_node_id, weight) in self.weighted_neighbours(round_winner.node_num) {
+                if distances[adjacent_node_id - 1].is_some() {
+                    continue;
+                }
+                let candidate = distance + weight;
+                let current = heap.delete_by_id(adjacent_node_id).and_then(|score| score.score);
+                heap.insert(DijkstraScore::from(adjacent_node_id, current.map_or(candidate, |current| current.min(candidate))));
+            }
+        }
+        distances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::{distances::ShortestPathAlgorithm, graph::UWGraph};
+
+    fn random_edges(rng: &mut XorShift64, node_count: usize, weights: &[usize]) -> Vec<(usize, usize, usize)> {
+        (0..rng.below(3 * node_count))
+            .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count), weights[rng.below(weights.len())]))
+            .collect()
+    }
+
+    // Bellman-Ford over the undirected edges, independent of every algorithm under test
+    fn bellman_ford(node_count: usize, edges: &[(usize, usize, usize)], source: usize) -> Vec<Option<usize>> {
+        let mut distances: Vec<Option<usize>> = vec![None; node_count];
+        distances[source - 1] = Some(0);
+        for _ in 0..node_count {
+            for (from, to, weight) in edges.iter().copied() {
+                for (tail, head) in [(from, to), (to, from)] {
+                    if let Some(distance) = distances[tail - 1] {
+                        if distances[head - 1].is_none_or(|current| distance + weight < current) {
+                            distances[head - 1] = Some(distance + weight);
+                        }
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn picks_algorithm_by_weights() {
+        let test_data_as_txt = utils::read_as_string(File::open("./test_data/1.txt").unwrap());
+        let graph = UWGraph::from_text(test_data_as_txt);
+        assert_eq!(graph.shortest_path_algorithm(), ShortestPathAlgorithm::Dial);
+        assert_eq!(graph.distances_from(1), (1..=8).map(|node_id| graph.shortest_path_to(node_id)).collect::<Vec<_>>());
+
+        let zero_one = UWGraph::from_edges(3, &[(1, 2, 1), (2, 3, 0), (1, 3, 1)]);
+        assert_eq!(zero_one.shortest_path_algorithm(), ShortestPathAlgorithm::ZeroOneBreadthFirst);
+        assert_eq!(zero_one.distances_from(3), vec![Some(1), Some(0), Some(0)]);
+
+        let uniform = UWGraph::from_edges(3, &[(1, 2, 5), (2, 3, 5)]);
+        assert_eq!(uniform.shortest_path_algorithm(), ShortestPathAlgorithm::BreadthFirst);
+        assert_eq!(uniform.distances_from(1), vec![Some(0), Some(5), Some(10)]);
+
+        let small = UWGraph::from_edges(3, &[(1, 2, 5), (2, 3, 2), (1, 3, 8)]);
+        assert_eq!(small.shortest_path_algorithm(), ShortestPathAlgorithm::Dial);
+        assert_eq!(small.distances_from(3), vec![Some(7), Some(2), Some(0)]);
+
+        let large = UWGraph::from_edges(4, &[(1, 2, 1000), (2, 3, 1)]);
+        assert_eq!(large.shortest_path_algorithm(), ShortestPathAlgorithm::Dijkstra);
+        assert_eq!(large.distances_from(3), vec![Some(1001), Some(1), Some(0), None]);
+    }
+
+    #[test]
+    fn algorithms_agree_with_bellman_ford() {
+        let mut rng = XorShift64::new(3);
+        for max_weight in [0, 1, 9, 64, 1000] {
+            for _ in 0..300 {
+                let node_count = 1 + rng.below(120);
+                let weights = (0..=max_weight).collect::<Vec<usize>>();
+                let edges = random_edges(&mut rng, node_count, &weights);
+                let graph = UWGraph::from_edges(node_count, &edges);
+                let expected = bellman_ford(node_count, &edges, 1);
+
+                assert_eq!(graph.distances_from(1), expected);
+                assert_eq!(graph.distances_with(1, ShortestPathAlgorithm::Dial), expected);
+                assert_eq!(graph.distances_with(1, ShortestPathAlgorithm::Dijkstra), expected);
+                if max_weight <= 1 {
+                    assert_eq!(graph.distances_with(1, ShortestPathAlgorithm::ZeroOneBreadthFirst), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn breadth_first_agrees_with_bellman_ford_on_uniform_weights() {
+        let mut rng = XorShift64::new(4);
+        for _ in 0..200 {
+            let node_count = 1 + rng.below(40);
+            let weight = rng.below(1000);
+            let edges = random_edges(&mut rng, node_count, &[weight]);
+            let graph = UWGraph::from_edges(node_count, &edges);
+            let expected = bellman_ford(node_count, &edges, 1);
+
+            assert_eq!(graph.distances_with(1, ShortestPathAlgorithm::BreadthFirst), expected);
+            assert_eq!(graph.distances_with(1, ShortestPathAlgorithm::Dijkstra), expected);
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use std::cmp::{min, Ordering};
 
+use graph_core::{Components, Graph, union_find, WeightedGraph};
+
 use crate::heap::{Heap, Identity};
 
 // undirected weighted graph
 #[derive(Debug, Clone, Default)]
-pub(crate) struct UWGraph {
+pub struct UWGraph {
     nodes: Vec<Node>,
 }
 
@@ -26,17 +28,27 @@ impl UWGraph {
                 .map(|tuple| Edge { node_num: tuple[0], weight: tuple[1] })
                 .collect::<Vec<Edge>>();
 
-            graph.nodes.push(Node { edges: edges })
+            graph.nodes.push(Node { edges })
         }
         graph
     }
 
+    /// Every `(from, to, weight)` edge is added in both directions
+    pub fn from_edges(node_count: usize, edges: &[(usize, usize, usize)]) -> Self {
+        let mut nodes = vec![Node { edges: Vec::new() }; node_count];
+        for (from, to, weight) in edges.iter().copied() {
+            assert!(from > 0 && to > 0, "Node ids start from 1");
+            nodes[from - 1].edges.push(Edge { node_num: to, weight });
+            nodes[to - 1].edges.push(Edge { node_num: from, weight });
+        }
+        UWGraph { nodes }
+    }
+
     /// Computes shortest path distance from source node (1)
     pub fn shortest_path_to(&self, node_id: usize) -> Option<usize> {
         assert!(node_id <= self.nodes.len());
-        let mut shortest_path_len = 0_usize;
         let mut heap: Heap<DijkstraScore> = Heap::default();
-        for (index, node) in self.nodes.iter().enumerate() {
+        for index in 0..self.nodes.len() {
             let score = match index {
                 0 => DijkstraScore { node_num: 1, score: Some(0) },
                 x => DijkstraScore { node_num: x + 1, score: None }
@@ -49,7 +61,7 @@ impl UWGraph {
             if round_winner.node_num == node_id {
                 return round_winner.score;
             }
-            shortest_path_len = round_winner.score.expect("Should extract reachable vertices");
+            let shortest_path_len = round_winner.score.expect("Should extract reachable vertices");
             // update heap to maintain invariant
             for adjacent_edge in &self.nodes[round_winner.node_num - 1].edges {
                 let next_node_old_score = heap
@@ -64,6 +76,27 @@ impl UWGraph {
         }
         None
     }
+
+    /// Connected components numbered by their smallest node id
+    pub fn connected_components(&self) -> Components {
+        union_find::connected_components(self)
+    }
+}
+
+impl Graph for UWGraph {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+        self.nodes[node_id - 1].edges.iter().map(|edge| edge.node_num)
+    }
+}
+
+impl WeightedGraph for UWGraph {
+    fn weighted_neighbours(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.nodes[node_id - 1].edges.iter().map(|edge| (edge.node_num, edge.weight))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -105,9 +138,9 @@ impl Ord for DijkstraScore {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self.score, other.score) {
             (None, None) => Ordering::Equal,
-            (None, Some(x)) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Greater,
             (Some(x), Some(y)) => x.cmp(&y),
-            (Some(x), None) => Ordering::Less
+            (Some(_), None) => Ordering::Less
         }
     }
 }
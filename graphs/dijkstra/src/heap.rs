@@ -17,16 +17,14 @@ impl<T: Identity + Ord + Default + Debug + Clone> Heap<T> {
     }
 
     fn bubble_up(&mut self, checked_pos: usize) {
-        if (checked_pos <= 1) {
+        if checked_pos <= 1 {
             return;
         }
         // floor(div by 2)
         let parent_pos = checked_pos >> 1;
         let parent_node = &self.data[parent_pos - 1];
         let child_node = &self.data[checked_pos - 1];
-        if parent_node <= child_node {
-            return;
-        } else {
+        if parent_node > child_node {
             self.swap_elements(parent_pos - 1, checked_pos - 1);
             self.bubble_up(parent_pos)
         }
@@ -40,7 +38,7 @@ impl<T: Identity + Ord + Default + Debug + Clone> Heap<T> {
 
     // should remove from heap
     pub fn extract_min(&mut self) -> Option<T> {
-        if self.data.len() == 0 {
+        if self.data.is_empty() {
             None
         } else {
             self.swap_elements(0, self.data.len() - 1);
@@ -58,7 +56,11 @@ impl<T: Identity + Ord + Default + Debug + Clone> Heap<T> {
             let i = self.locations[&id];
             self.swap_elements(i, self.data.len() - 1);
             let ans = self.pop_last();
-            self.bubble_down(i + 1);
+            // the former last item may belong above or below its new position
+            if i < self.data.len() {
+                self.bubble_up(i + 1);
+                self.bubble_down(i + 1);
+            }
             ans
         }
     }
@@ -76,7 +78,7 @@ impl<T: Identity + Ord + Default + Debug + Clone> Heap<T> {
         self.data.is_empty()
     }
     fn bubble_down(&mut self, checked_pos: usize) {
-        if (checked_pos == self.data.len()) {
+        if checked_pos == self.data.len() {
             return;
         }
         // multiply by 2
@@ -89,15 +91,11 @@ impl<T: Identity + Ord + Default + Debug + Clone> Heap<T> {
 
     fn check_child_inv(&mut self, checked_pos: usize, child_pos: usize) {
         if let Some(child) = self.data.get(child_pos - 1) {
-            if child >= &self.data[checked_pos - 1] {
-                // invariant holds
-                return;
-            } else {
+            // otherwise invariant holds
+            if child < &self.data[checked_pos - 1] {
                 self.swap_elements(checked_pos - 1, child_pos - 1);
                 self.bubble_down(child_pos);
             }
-        } else {
-            return;
         }
     }
 
@@ -113,3 +111,45 @@ pub trait Identity {
     fn get_id(&self) -> usize;
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::graph::DijkstraScore;
+    use crate::heap::Heap;
+
+    #[test]
+    fn delete_by_id_moves_last_item_up() {
+        let scores = [11, 44, 1, 12, 28, 15, 33, 21, 15, 43, 25, 1, 0];
+        let mut heap = Heap::<DijkstraScore>::default();
+        for (index, score) in scores.iter().enumerate() {
+            heap.insert(DijkstraScore::from(index + 1, *score));
+        }
+        // the last item takes the place of node 8, below a larger parent
+        heap.delete_by_id(8);
+
+        let extracted = std::iter::from_fn(|| heap.extract_min()).map(|item| item.score.unwrap()).collect::<Vec<_>>();
+        assert_eq!(extracted, vec![0, 1, 1, 11, 12, 15, 15, 25, 28, 33, 43, 44]);
+    }
+
+    #[test]
+    fn delete_by_id_keeps_order_on_random_heaps() {
+        let mut rng = utils::XorShift64::new(8);
+        for _ in 0..500 {
+            let scores = (0..2 + rng.below(30)).map(|_| rng.below(50)).collect::<Vec<usize>>();
+            let mut heap = Heap::<DijkstraScore>::default();
+            for (index, score) in scores.iter().enumerate() {
+                heap.insert(DijkstraScore::from(index + 1, *score));
+            }
+            let deleted = 1 + rng.below(scores.len());
+            heap.delete_by_id(deleted);
+
+            let mut expected = scores.iter()
+                .enumerate()
+                .filter(|(index, _)| index + 1 != deleted)
+                .map(|(_, score)| *score)
+                .collect::<Vec<usize>>();
+            expected.sort_unstable();
+            let extracted = std::iter::from_fn(|| heap.extract_min()).map(|item| item.score.unwrap()).collect::<Vec<_>>();
+            assert_eq!(extracted, expected);
+        }
+    }
+}
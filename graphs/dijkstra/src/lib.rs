@@ -1,11 +1,16 @@
-mod graph;
+pub mod biconnected;
+pub mod distances;
+pub mod graph;
 pub mod heap;
+pub mod stoer_wagner;
 
 #[cfg(test)]
 mod tests {
     use std::collections::BinaryHeap;
     use std::fs::File;
 
+    use graph_core::{Graph, scc, traversal, WeightedGraph};
+
     use crate::graph::{DijkstraScore, UWGraph};
     use crate::heap::Heap;
 
@@ -25,6 +30,30 @@ mod tests {
         assert_eq!(graph.shortest_path_to(8), Some(2));
     }
 
+    #[test]
+    fn generic_traversals() {
+        let test_data_as_txt = utils::read_as_string(File::open("./test_data/1.txt").unwrap());
+        let graph = UWGraph::from_text(test_data_as_txt);
+
+        assert_eq!(graph.node_count(), 8);
+        assert_eq!(graph.neighbours(1).collect::<Vec<usize>>(), vec![2, 8]);
+        assert_eq!(graph.weighted_neighbours(1).collect::<Vec<(usize, usize)>>(), vec![(2, 1), (8, 2)]);
+        assert_eq!(traversal::breadth_first(&graph, 1), vec![1, 2, 8, 3, 7, 4, 6, 5]);
+        // every edge is listed in both directions
+        assert_eq!(scc::strongly_connected_components(&graph).count(), 1);
+    }
+
+    #[test]
+    fn connected_components() {
+        let graph = UWGraph::from_text("1\t2,4\n2\t1,4\n3\n4\t5,1\n5\t4,1".to_string());
+        let components = graph.connected_components();
+
+        assert_eq!(components.membership(), &[1, 1, 2, 3, 3]);
+        assert_eq!(components.largest_sizes_padded(3, 0), vec![2, 2, 1]);
+        let test_data_as_txt = utils::read_as_string(File::open("./test_data/1.txt").unwrap());
+        assert_eq!(UWGraph::from_text(test_data_as_txt).connected_components().count(), 1);
+    }
+
     #[test]
     fn test_binary_heap() {
         let mut custom_heap = Heap::<DijkstraScore>::default();
@@ -49,7 +78,7 @@ mod tests {
         custom_heap.insert(DijkstraScore::from(6, 7));
         custom_heap.insert(DijkstraScore::from(5, 6));
         custom_heap.insert(DijkstraScore::from(4, 5));
-        /// delete element
+        // delete element
         assert_eq!(custom_heap.delete_by_id(1_usize).unwrap(), DijkstraScore::from(1, 3));
         assert_eq!(custom_heap.delete_by_id(2_usize).unwrap(), DijkstraScore::from(2, 2));
         assert_eq!(custom_heap.extract_min().unwrap(), DijkstraScore::from(4, 5));
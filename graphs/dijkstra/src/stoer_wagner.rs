@@ -0,0 +1,168 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use graph_core::{Graph, WeightedGraph};
+
+use crate::{graph::UWGraph, heap::{Heap, Identity}};
+
+/// Cut with its two sides, both ascending; `left` holds node 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedCut {
+    /// total weight of the edges crossing the cut
+    pub weight: usize,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+// weight of the edges from a supernode to the ones already added in the current phase;
+// ordered heaviest first, so the min-heap hands out the most tightly connected supernode
+#[derive(Eq, PartialEq, Debug, Copy, Clone, Default)]
+struct Connection {
+    node_num: usize,
+    weight: usize,
+}
+
+impl Identity for Connection {
+    fn get_id(&self) -> usize {
+        self.node_num
+    }
+}
+
+impl Ord for Connection {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.weight.cmp(&self.weight).then(self.node_num.cmp(&other.node_num))
+    }
+}
+
+impl PartialOrd for Connection {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl UWGraph {
+    /// Deterministic global minimum cut (Stoer-Wagner): n - 1 maximum adjacency phases,
+    /// each merging the last two supernodes it orders. None for fewer than two nodes.
+    pub fn stoer_wagner(&self) -> Option<WeightedCut> {
+        let node_count = self.node_count();
+        if node_count < 2 {
+            return None;
+        }
+        // summed weights between supernodes, edges are listed at both ends
+        let mut adjacent: Vec<HashMap<usize, usize>> = vec![HashMap::new(); node_count];
+        for node_id in 1..=node_count {
+            for (adjacent_node_id, weight) in self.weighted_neighbours(node_id) {
+                if adjacent_node_id != node_id {
+                    *adjacent[node_id - 1].entry(adjacent_node_id).or_insert(0) += weight;
+                }
+            }
+        }
+        // original nodes merged into every supernode, empty once merged away
+        let mut members: Vec<Vec<usize>> = (1..=node_count).map(|node_id| vec![node_id]).collect();
+        let mut active: Vec<usize> = (1..=node_count).collect();
+
+        let mut best: Option<(usize, Vec<usize>)> = None;
+        while active.len() > 1 {
+            let (s, t, cut_of_phase) = maximum_adjacency_phase(&active, &adjacent);
+            if best.as_ref().is_none_or(|(weight, _)| cut_of_phase < *weight) {
+                best = Some((cut_of_phase, members[t - 1].clone()));
+            }
+
+            // merge t into s
+            for (node_id, weight) in std::mem::take(&mut adjacent[t - 1]) {
+                adjacent[node_id - 1].remove(&t);
+                if node_id != s {
+                    *adjacent[s - 1].entry(node_id).or_insert(0) += weight;
+                    *adjacent[node_id - 1].entry(s).or_insert(0) += weight;
+                }
+            }
+            let merged = std::mem::take(&mut members[t - 1]);
+            members[s - 1].extend(merged);
+            active.retain(|node_id| *node_id != t);
+        }
+
+        let (weight, side) = best.unwrap();
+        let mut in_side = vec![false; node_count];
+        side.iter().for_each(|node_id| in_side[node_id - 1] = true);
+        let (left, right) = (1..=node_count).partition(|node_id| in_side[node_id - 1] == in_side[0]);
+        Some(WeightedCut { weight, left, right })
+    }
+}
+
+// adds supernodes one by one, always the one most tightly connected to those added so far;
+// returns the last two with the weight connecting the last one to all the others
+fn maximum_adjacency_phase(active: &[usize], adjacent: &[HashMap<usize, usize>]) -> (usize, usize, usize) {
+    let mut heap: Heap<Connection> = Heap::default();
+    for node_id in active {
+        heap.insert(Connection { node_num: *node_id, weight: 0 });
+    }
+    let (mut previous, mut last, mut last_weight) = (0, 0, 0);
+    while let Some(connection) = heap.extract_min() {
+        (previous, last, last_weight) = (last, connection.node_num, connection.weight);
+        for (node_id, weight) in adjacent[last - 1].iter() {
+            if let Some(current) = heap.delete_by_id(*node_id) {
+                heap.insert(Connection { node_num: *node_id, weight: current.weight + weight });
+            }
+        }
+    }
+    (previous, last, last_weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::XorShift64;
+
+    use crate::{graph::UWGraph, stoer_wagner::WeightedCut};
+
+    // lightest cut over every partition with node 1 on the left
+    fn brute_force(node_count: usize, edges: &[(usize, usize, usize)]) -> usize {
+        (1..1_usize << (node_count - 1))
+            .map(|mask| {
+                let left = |node_id: usize| node_id == 1 || mask & (1 << (node_id - 2)) == 0;
+                edges.iter()
+                    .filter(|(from, to, _)| left(*from) != left(*to))
+                    .map(|(_, _, weight)| weight)
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn cuts_example_from_the_paper() {
+        let edges = [(1, 2, 2), (1, 5, 3), (2, 3, 3), (2, 5, 2), (2, 6, 2), (3, 4, 4),
+            (3, 7, 2), (4, 7, 2), (4, 8, 2), (5, 6, 3), (6, 7, 1), (7, 8, 3)];
+        let graph = UWGraph::from_edges(8, &edges);
+
+        assert_eq!(graph.stoer_wagner(), Some(WeightedCut { weight: 4, left: vec![1, 2, 5, 6], right: vec![3, 4, 7, 8] }));
+    }
+
+    #[test]
+    fn disconnected_and_tiny_graphs() {
+        let graph = UWGraph::from_edges(4, &[(1, 2, 5), (3, 4, 1)]);
+
+        assert_eq!(graph.stoer_wagner().unwrap().weight, 0);
+        assert_eq!(UWGraph::from_edges(1, &[]).stoer_wagner(), None);
+        assert_eq!(UWGraph::from_edges(2, &[(1, 2, 3), (1, 2, 4), (2, 2, 9)]).stoer_wagner().unwrap().weight, 7);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = XorShift64::new(23);
+        for _ in 0..100 {
+            let node_count = 2 + rng.below(9);
+            let edges = (0..node_count + rng.below(3 * node_count))
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count), rng.below(10)))
+                .collect::<Vec<_>>();
+            let cut = UWGraph::from_edges(node_count, &edges).stoer_wagner().unwrap();
+
+            assert_eq!(cut.weight, brute_force(node_count, &edges));
+            let crossing: usize = edges.iter()
+                .filter(|(from, to, _)| cut.left.contains(from) != cut.left.contains(to))
+                .map(|(_, _, weight)| weight)
+                .sum();
+            assert_eq!(crossing, cut.weight);
+            assert_eq!(cut.left[0], 1);
+            assert_eq!(cut.left.len() + cut.right.len(), node_count);
+        }
+    }
+}
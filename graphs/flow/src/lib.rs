@@ -0,0 +1,206 @@
+use graph_core::{Graph, WeightedGraph};
+
+pub mod max_flow;
+pub mod min_cost;
+
+/// Directed edge with the most flow it can carry and the cost of every unit of flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub capacity: usize,
+    pub cost: usize,
+}
+
+/// Directed capacity network with nodes numbered from 1; parallel edges are kept apart
+#[derive(Debug, Clone, Default)]
+pub struct FlowNetwork {
+    edges: Vec<Edge>,
+    // indices of the edges leaving every node, indexed by node_id - 1
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    /// Same format as `UWGraph::from_text`: a node id followed by tab-separated
+    /// `to,capacity` pairs, but every pair is a single directed edge;
+    /// `to,capacity,cost` triples give a cost per unit of flow, 0 otherwise
+    pub fn from_text(text: String) -> Self {
+        let mut network = FlowNetwork::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let node_and_edges = line.trim_end().split('\t').collect::<Vec<&str>>();
+            let from = node_and_edges[0].parse::<usize>().expect("Parsing error");
+            network.ensure_allocation_for(from);
+            for pair in node_and_edges[1..].iter() {
+                let tuple = pair.split(',')
+                    .map(|number| number.parse::<usize>().expect("Parsing error"))
+                    .collect::<Vec<usize>>();
+                network.add_edge_with_cost(from, tuple[0], tuple[1], tuple.get(2).copied().unwrap_or(0));
+            }
+        }
+        network
+    }
+
+    /// `(from, to, capacity)` edges, their indices follow the slice order
+    pub fn from_edges(node_count: usize, edges: &[(usize, usize, usize)]) -> Self {
+        let mut network = FlowNetwork::default();
+        network.ensure_allocation_for(node_count);
+        for (from, to, capacity) in edges.iter().copied() {
+            network.add_edge(from, to, capacity);
+        }
+        network
+    }
+
+    /// `(from, to, capacity, cost)` edges, their indices follow the slice order
+    pub fn from_costed_edges(node_count: usize, edges: &[(usize, usize, usize, usize)]) -> Self {
+        let mut network = FlowNetwork::default();
+        network.ensure_allocation_for(node_count);
+        for (from, to, capacity, cost) in edges.iter().copied() {
+            network.add_edge_with_cost(from, to, capacity, cost);
+        }
+        network
+    }
+
+    /// Returns the index of the new edge
+    pub fn add_edge(&mut self, from: usize, to: usize, capacity: usize) -> usize {
+        self.add_edge_with_cost(from, to, capacity, 0)
+    }
+
+    pub fn add_edge_with_cost(&mut self, from: usize, to: usize, capacity: usize, cost: usize) -> usize {
+        assert!(from > 0 && to > 0, "Node ids start from 1");
+        self.ensure_allocation_for(from.max(to));
+        self.outgoing[from - 1].push(self.edges.len());
+        self.edges.push(Edge { from, to, capacity, cost });
+        self.edges.len() - 1
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    pub fn edges(&self) -> &[Edge] {
+        &self.edges
+    }
+
+    fn ensure_allocation_for(&mut self, count: usize) {
+        if self.outgoing.len() < count {
+            self.outgoing.resize(count, Vec::new());
+        }
+    }
+}
+
+impl Graph for FlowNetwork {
+    fn node_count(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+        self.outgoing[node_id - 1].iter().map(|index| self.edges[*index].to)
+    }
+}
+
+impl WeightedGraph for FlowNetwork {
+    /// Capacities as weights
+    fn weighted_neighbours(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.outgoing[node_id - 1].iter().map(|index| (self.edges[*index].to, self.edges[*index].capacity))
+    }
+}
+
+/// Residual network: arc 2k is edge k with its remaining capacity,
+/// arc 2k + 1 goes back along edge k with the capacity of its flow
+#[derive(Debug, Clone)]
+pub(crate) struct Residual {
+    heads: Vec<usize>,
+    capacities: Vec<usize>,
+    // arcs leaving every node, indexed by node_id - 1
+    arcs: Vec<Vec<usize>>,
+}
+
+impl Residual {
+    pub(crate) fn new(network: &FlowNetwork) -> Self {
+        let mut residual = Residual {
+            heads: Vec::with_capacity(2 * network.edges.len()),
+            capacities: Vec::with_capacity(2 * network.edges.len()),
+            arcs: vec![Vec::new(); network.node_count()],
+        };
+        for (index, edge) in network.edges.iter().enumerate() {
+            residual.heads.push(edge.to);
+            residual.capacities.push(edge.capacity);
+            residual.arcs[edge.from - 1].push(2 * index);
+            residual.heads.push(edge.from);
+            residual.capacities.push(0);
+            residual.arcs[edge.to - 1].push(2 * index + 1);
+        }
+        residual
+    }
+
+    pub(crate) fn head(&self, arc: usize) -> usize {
+        self.heads[arc]
+    }
+
+    pub(crate) fn tail(&self, arc: usize) -> usize {
+        self.heads[arc ^ 1]
+    }
+
+    pub(crate) fn capacity(&self, arc: usize) -> usize {
+        self.capacities[arc]
+    }
+
+    pub(crate) fn arcs(&self, node_id: usize) -> &[usize] {
+        &self.arcs[node_id - 1]
+    }
+
+    pub(crate) fn push(&mut self, arc: usize, amount: usize) {
+        self.capacities[arc] -= amount;
+        self.capacities[arc ^ 1] += amount;
+    }
+
+    /// Flow on every edge of the network
+    pub(crate) fn flows(&self) -> Vec<usize> {
+        self.capacities.iter().skip(1).step_by(2).copied().collect()
+    }
+
+    /// Nodes reachable from `source` through arcs with capacity left
+    pub(crate) fn reachable_from(&self, source: usize) -> Vec<bool> {
+        let mut reachable = vec![false; self.arcs.len()];
+        let mut stack = vec![source];
+        reachable[source - 1] = true;
+        while let Some(node_id) = stack.pop() {
+            for arc in self.arcs(node_id) {
+                let head = self.heads[*arc];
+                if self.capacities[*arc] > 0 && !reachable[head - 1] {
+                    reachable[head - 1] = true;
+                    stack.push(head);
+                }
+            }
+        }
+        reachable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use graph_core::{Graph, WeightedGraph};
+
+    use crate::FlowNetwork;
+
+    #[test]
+    fn parses_directed_edges() {
+        let network = FlowNetwork::from_text(utils::read_as_string(File::open("./test_data/1.txt").unwrap()));
+
+        assert_eq!(network.node_count(), 6);
+        assert_eq!(network.edges().len(), 9);
+        assert_eq!(network.neighbours(3).collect::<Vec<usize>>(), vec![2, 5]);
+        assert_eq!(network.weighted_neighbours(1).collect::<Vec<(usize, usize)>>(), vec![(2, 16), (3, 13)]);
+        assert_eq!(network.neighbours(6).count(), 0);
+    }
+
+    #[test]
+    fn parses_optional_costs() {
+        let network = FlowNetwork::from_text("1\t2,5,3\t3,4\n2\t3,1,7".to_string());
+
+        assert_eq!(network.edges().iter().map(|edge| edge.cost).collect::<Vec<usize>>(), vec![3, 0, 7]);
+        assert_eq!(network.edges()[0].capacity, 5);
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::VecDeque;
+
+use crate::{FlowNetwork, Residual};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow {
+    pub value: usize,
+    /// flow on every edge, in the order of [`FlowNetwork::edges`]
+    pub flows: Vec<usize>,
+    pub cut: StCut,
+}
+
+/// Minimum s-t cut: its capacity equals the maximum flow value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StCut {
+    /// nodes still reachable from the source in the residual network, ascending
+    pub source_side: Vec<usize>,
+    pub sink_side: Vec<usize>,
+    /// indices of the edges from the source side to the sink side, all saturated
+    pub edges: Vec<usize>,
+}
+
+impl FlowNetwork {
+    /// Augments along shortest residual paths found by breadth-first search, O(V E^2)
+    pub fn edmonds_karp(&self, source: usize, sink: usize) -> MaxFlow {
+        self.assert_terminals(source, sink);
+        let mut residual = Residual::new(self);
+        let mut value = 0;
+        loop {
+            // arc used to reach every node, indexed by node_id - 1
+            let mut reached_by: Vec<Option<usize>> = vec![None; self.node_count()];
+            let mut queue = VecDeque::from([source]);
+            while let Some(node_id) = queue.pop_front() {
+                if node_id == sink {
+                    break;
+                }
+                for arc in residual.arcs(node_id) {
+                    let head = residual.head(*arc);
+                    if residual.capacity(*arc) > 0 && head != source && reached_by[head - 1].is_none() {
+                        reached_by[head - 1] = Some(*arc);
+                        queue.push_back(head);
+                    }
+                }
+            }
+            if reached_by[sink - 1].is_none() {
+                break;
+            }
+
+            let mut path = Vec::new();
+            let mut node_id = sink;
+            while let Some(arc) = reached_by[node_id - 1] {
+                path.push(arc);
+                node_id = residual.tail(arc);
+            }
+            value += augment(&mut residual, &path);
+        }
+        self.max_flow(value, &residual, source)
+    }
+
+    /// Blocking flows on the level graph of every breadth-first search, O(V^2 E)
+    pub fn dinic(&self, source: usize, sink: usize) -> MaxFlow {
+        self.assert_terminals(source, sink);
+        let mut residual = Residual::new(self);
+        let mut value = 0;
+        while let Some(mut levels) = levels(&residual, source, sink) {
+            // first arc of every node that may still lead to the sink
+            let mut next_arc = vec![0; self.node_count()];
+            let mut path: Vec<usize> = Vec::new();
+            let mut node_id = source;
+            loop {
+                if node_id == sink {
+                    value += augment(&mut residual, &path);
+                    path.clear();
+                    node_id = source;
+                    continue;
+                }
+                let arcs = residual.arcs(node_id);
+                while next_arc[node_id - 1] < arcs.len() {
+                    let arc = arcs[next_arc[node_id - 1]];
+                    let head = residual.head(arc);
+                    if residual.capacity(arc) > 0 && levels[head - 1] == levels[node_id - 1].map(|level| level + 1) {
+                        break;
+                    }
+                    next_arc[node_id - 1] += 1;
+                }
+                if next_arc[node_id - 1] < arcs.len() {
+                    let arc = arcs[next_arc[node_id - 1]];
+                    path.push(arc);
+                    node_id = residual.head(arc);
+                    continue;
+                }
+                // dead end, nothing can pass through it until the next level graph
+                levels[node_id - 1] = None;
+                match path.pop() {
+                    Some(arc) => {
+                        node_id = residual.tail(arc);
+                        next_arc[node_id - 1] += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+        self.max_flow(value, &residual, source)
+    }
+
+    fn assert_terminals(&self, source: usize, sink: usize) {
+        assert!(source > 0 && source <= self.node_count(), "Unknown source {}", source);
+        assert!(sink > 0 && sink <= self.node_count(), "Unknown sink {}", sink);
+        assert_ne!(source, sink, "Source and sink should differ");
+    }
+
+    fn max_flow(&self, value: usize, residual: &Residual, source: usize) -> MaxFlow {
+        let reachable = residual.reachable_from(source);
+        let (source_side, sink_side) = (1..=self.node_count()).partition(|node_id| reachable[node_id - 1]);
+        let edges = self.edges.iter()
+            .enumerate()
+            .filter(|(_, edge)| reachable[edge.from - 1] && !reachable[edge.to - 1])
+            .map(|(index, _)| index)
+            .collect();
+        MaxFlow { value, flows: residual.flows(), cut: StCut { source_side, sink_side, edges } }
+    }
+}
+
+// pushes the bottleneck capacity along the path and returns it
+fn augment(residual: &mut Residual, path: &[usize]) -> usize {
+    let bottleneck = path.iter().map(|arc| residual.capacity(*arc)).min().unwrap_or(0);
+    for arc in path {
+        residual.push(*arc, bottleneck);
+    }
+    bottleneck
+}
+
+// breadth-first distances from the source through arcs with capacity left,
+// None once the sink is unreachable
+fn levels(residual: &Residual, source: usize, sink: usize) -> Option<Vec<Option<usize>>> {
+    let mut levels: Vec<Option<usize>> = vec![None; residual.arcs.len()];
+    let mut queue = VecDeque::from([source]);
+    levels[source - 1] = Some(0);
+    while let Some(node_id) = queue.pop_front() {
+        for arc in residual.arcs(node_id) {
+            let head = residual.head(*arc);
+            if residual.capacity(*arc) > 0 && levels[head - 1].is_none() {
+                levels[head - 1] = levels[node_id - 1].map(|level| level + 1);
+                queue.push_back(head);
+            }
+        }
+    }
+    levels[sink - 1].map(|_| levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::{FlowNetwork, max_flow::MaxFlow};
+
+    fn assert_valid(network: &FlowNetwork, flow: &MaxFlow, source: usize, sink: usize) {
+        let mut balance = vec![0_i64; network.node_count()];
+        for (edge, amount) in network.edges().iter().zip(flow.flows.iter()) {
+            assert!(*amount <= edge.capacity);
+            balance[edge.from - 1] -= *amount as i64;
+            balance[edge.to - 1] += *amount as i64;
+        }
+        for node_id in 1..=network.node_count() {
+            let expected = match node_id {
+                _ if node_id == source => -(flow.value as i64),
+                _ if node_id == sink => flow.value as i64,
+                _ => 0,
+            };
+            assert_eq!(balance[node_id - 1], expected);
+        }
+        let cut_capacity: usize = flow.cut.edges.iter().map(|index| network.edges()[*index].capacity).sum();
+        assert_eq!(cut_capacity, flow.value);
+        assert!(flow.cut.source_side.contains(&source));
+        assert!(flow.cut.sink_side.contains(&sink));
+    }
+
+    // lightest s-t cut over every partition
+    fn brute_force(network: &FlowNetwork, source: usize, sink: usize) -> usize {
+        let node_count = network.node_count();
+        (0..1_usize << node_count)
+            .filter(|mask| mask & (1 << (source - 1)) != 0 && mask & (1 << (sink - 1)) == 0)
+            .map(|mask| {
+                network.edges().iter()
+                    .filter(|edge| mask & (1 << (edge.from - 1)) != 0 && mask & (1 << (edge.to - 1)) == 0)
+                    .map(|edge| edge.capacity)
+                    .sum()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn classic_network() {
+        let network = FlowNetwork::from_text(utils::read_as_string(File::open("./test_data/1.txt").unwrap()));
+
+        for flow in [network.edmonds_karp(1, 6), network.dinic(1, 6)] {
+            assert_eq!(flow.value, 23);
+            assert_valid(&network, &flow, 1, 6);
+            assert_eq!(flow.cut.source_side, vec![1, 2, 3, 5]);
+            // 2 -> 4, 5 -> 4 and 5 -> 6
+            assert_eq!(flow.cut.edges, vec![2, 7, 8]);
+        }
+    }
+
+    #[test]
+    fn disconnected_sink() {
+        let network = FlowNetwork::from_edges(4, &[(1, 2, 5), (3, 4, 5)]);
+
+        for flow in [network.edmonds_karp(1, 4), network.dinic(1, 4)] {
+            assert_eq!(flow.value, 0);
+            assert_eq!(flow.flows, vec![0, 0]);
+            assert_eq!(flow.cut.source_side, vec![1, 2]);
+            assert!(flow.cut.edges.is_empty());
+        }
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = XorShift64::new(31);
+        for _ in 0..100 {
+            let node_count = 2 + rng.below(8);
+            let edges = (0..rng.below(4 * node_count))
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count), rng.below(20)))
+                .collect::<Vec<_>>();
+            let network = FlowNetwork::from_edges(node_count, &edges);
+            let expected = brute_force(&network, 1, node_count);
+
+            for flow in [network.edmonds_karp(1, node_count), network.dinic(1, node_count)] {
+                assert_eq!(flow.value, expected);
+                assert_valid(&network, &flow, 1, node_count);
+            }
+        }
+    }
+}
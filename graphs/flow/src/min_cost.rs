@@ -0,0 +1,208 @@
+use dijkstra::{graph::DijkstraScore, heap::Heap};
+
+use crate::{FlowNetwork, Residual};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCostFlow {
+    pub value: usize,
+    /// sum of flow times cost over all edges
+    pub cost: usize,
+    /// flow on every edge, in the order of [`FlowNetwork::edges`]
+    pub flows: Vec<usize>,
+}
+
+impl FlowNetwork {
+    /// Cheapest among the maximum flows
+    pub fn min_cost_max_flow(&self, source: usize, sink: usize) -> MinCostFlow {
+        self.successive_shortest_paths(source, sink, usize::MAX)
+    }
+
+    /// Cheapest flow of exactly `demand` units, None if the network can't carry that much
+    pub fn min_cost_flow(&self, source: usize, sink: usize, demand: usize) -> Option<MinCostFlow> {
+        let flow = self.successive_shortest_paths(source, sink, demand);
+        (flow.value == demand).then_some(flow)
+    }
+
+    // augments along cheapest residual paths until `demand` is met or the sink is cut off;
+    // node potentials (Johnson's reweighting) keep reduced costs non-negative for Dijkstra
+    fn successive_shortest_paths(&self, source: usize, sink: usize, demand: usize) -> MinCostFlow {
+        assert!(source > 0 && source <= self.node_count(), "Unknown source {}", source);
+        assert!(sink > 0 && sink <= self.node_count(), "Unknown sink {}", sink);
+        assert_ne!(source, sink, "Source and sink should differ");
+        let mut residual = Residual::new(self);
+        // costs are non-negative, so zero potentials start out valid
+        let mut potentials = vec![0_i64; self.node_count()];
+        let (mut value, mut cost) = (0, 0);
+        while value < demand {
+            let (distances, reached_by) = self.cheapest_paths(&residual, &potentials, source);
+            if distances[sink - 1].is_none() {
+                break;
+            }
+            for (potential, distance) in potentials.iter_mut().zip(distances.iter()) {
+                if let Some(distance) = distance {
+                    *potential += *distance as i64;
+                }
+            }
+
+            let mut path = Vec::new();
+            let mut node_id = sink;
+            while let Some(arc) = reached_by[node_id - 1] {
+                path.push(arc);
+                node_id = residual.tail(arc);
+            }
+            let amount = path.iter()
+                .map(|arc| residual.capacity(*arc))
+                .min()
+                .unwrap()
+                .min(demand - value);
+            path.iter().for_each(|arc| residual.push(*arc, amount));
+            value += amount;
+            // reduced costs telescope, so the real path cost is the difference of the new potentials
+            let path_cost = potentials[sink - 1] - potentials[source - 1];
+            cost += amount * path_cost as usize;
+        }
+        MinCostFlow { value, cost, flows: residual.flows() }
+    }
+
+    // Dijkstra over reduced costs, returns the distance of every node
+    // and the arc its cheapest path arrives by
+    fn cheapest_paths(&self, residual: &Residual, potentials: &[i64], source: usize)
+                      -> (Vec<Option<usize>>, Vec<Option<usize>>) {
+        let mut distances: Vec<Option<usize>> = vec![None; self.node_count()];
+        let mut reached_by: Vec<Option<usize>> = vec![None; self.node_count()];
+        let mut heap: Heap<DijkstraScore> = Heap::default();
+        heap.insert(DijkstraScore::from(source, 0));
+        while let Some(round_winner) = heap.extract_min() {
+            let node_id = round_winner.node_num;
+            let distance = round_winner.score.expect("Only reached nodes get into the heap");
+            distances[node_id - 1] = Some(distance);
+            for arc in residual.arcs(node_id) {
+                let head = residual.head(*arc);
+                if residual.capacity(*arc) == 0 || distances[head - 1].is_some() {
+                    continue;
+                }
+                let reduced_cost = self.arc_cost(*arc) + potentials[node_id - 1] - potentials[head - 1];
+                debug_assert!(reduced_cost >= 0, "Potentials should keep reduced costs non-negative");
+                let candidate = distance + reduced_cost as usize;
+                let current = heap.delete_by_id(head).and_then(|score| score.score);
+                match current {
+                    Some(current) if current <= candidate => heap.insert(DijkstraScore::from(head, current)),
+                    _ => {
+                        reached_by[head - 1] = Some(*arc);
+                        heap.insert(DijkstraScore::from(head, candidate));
+                    }
+                }
+            }
+        }
+        (distances, reached_by)
+    }
+
+    // pushing flow back along an edge refunds its cost
+    fn arc_cost(&self, arc: usize) -> i64 {
+        let cost = self.edges[arc / 2].cost as i64;
+        if arc.is_multiple_of(2) { cost } else { -cost }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::XorShift64;
+
+    use crate::{FlowNetwork, min_cost::MinCostFlow};
+
+    fn assert_valid(network: &FlowNetwork, flow: &MinCostFlow, source: usize, sink: usize) {
+        let mut balance = vec![0_i64; network.node_count()];
+        let mut cost = 0;
+        for (edge, amount) in network.edges().iter().zip(flow.flows.iter()) {
+            assert!(*amount <= edge.capacity);
+            balance[edge.from - 1] -= *amount as i64;
+            balance[edge.to - 1] += *amount as i64;
+            cost += amount * edge.cost;
+        }
+        assert_eq!(cost, flow.cost);
+        for node_id in (1..=network.node_count()).filter(|node_id| *node_id != source && *node_id != sink) {
+            assert_eq!(balance[node_id - 1], 0);
+        }
+        assert_eq!(balance[sink - 1], flow.value as i64);
+    }
+
+    // a flow is cheapest for its value iff its residual network has no negative cycle
+    fn has_negative_cycle(network: &FlowNetwork, flows: &[usize]) -> bool {
+        let node_count = network.node_count();
+        let mut distances = vec![vec![i64::MAX; node_count]; node_count];
+        (0..node_count).for_each(|index| distances[index][index] = 0);
+        for (edge, amount) in network.edges().iter().zip(flows.iter()) {
+            let (from, to, cost) = (edge.from - 1, edge.to - 1, edge.cost as i64);
+            if *amount < edge.capacity {
+                distances[from][to] = distances[from][to].min(cost);
+            }
+            if *amount > 0 {
+                distances[to][from] = distances[to][from].min(-cost);
+            }
+        }
+        for middle in 0..node_count {
+            for from in 0..node_count {
+                for to in 0..node_count {
+                    if distances[from][middle] != i64::MAX && distances[middle][to] != i64::MAX {
+                        distances[from][to] = distances[from][to].min(distances[from][middle] + distances[middle][to]);
+                    }
+                }
+            }
+        }
+        (0..node_count).any(|index| distances[index][index] < 0)
+    }
+
+    #[test]
+    fn assignment_problem() {
+        // workers 2..=4 to jobs 5..=7 through source 1 and sink 8
+        let costs = [[9, 2, 7], [6, 4, 3], [5, 8, 1]];
+        let mut network = FlowNetwork::default();
+        for (worker, job_costs) in costs.iter().enumerate() {
+            network.add_edge(1, worker + 2, 1);
+            network.add_edge(worker + 5, 8, 1);
+            for (job, cost) in job_costs.iter().enumerate() {
+                network.add_edge_with_cost(worker + 2, job + 5, 1, *cost);
+            }
+        }
+        let flow = network.min_cost_max_flow(1, 8);
+
+        assert_eq!(flow.value, 3);
+        // worker 1 takes job 2, worker 2 job 1 and worker 3 job 3
+        assert_eq!(flow.cost, 2 + 6 + 1);
+        assert_valid(&network, &flow, 1, 8);
+    }
+
+    #[test]
+    fn flow_for_demand() {
+        // cheap narrow route 1 -> 2 -> 4 and an expensive wide one 1 -> 3 -> 4
+        let network = FlowNetwork::from_costed_edges(4, &[(1, 2, 2, 1), (2, 4, 2, 1), (1, 3, 5, 4), (3, 4, 5, 4)]);
+
+        assert_eq!(network.min_cost_flow(1, 4, 0), Some(MinCostFlow { value: 0, cost: 0, flows: vec![0; 4] }));
+        assert_eq!(network.min_cost_flow(1, 4, 2).unwrap().cost, 4);
+        assert_eq!(network.min_cost_flow(1, 4, 3).unwrap().flows, vec![2, 2, 1, 1]);
+        assert_eq!(network.min_cost_flow(1, 4, 3).unwrap().cost, 12);
+        assert_eq!(network.min_cost_flow(1, 4, 8), None);
+        assert_eq!(network.min_cost_max_flow(1, 4).value, 7);
+    }
+
+    #[test]
+    fn cheapest_on_random_networks() {
+        let mut rng = XorShift64::new(37);
+        for _ in 0..100 {
+            let node_count = 2 + rng.below(8);
+            let edges = (0..rng.below(4 * node_count))
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count), rng.below(10), rng.below(10)))
+                .collect::<Vec<_>>();
+            let network = FlowNetwork::from_costed_edges(node_count, &edges);
+            let flow = network.min_cost_max_flow(1, node_count);
+
+            assert_eq!(flow.value, network.dinic(1, node_count).value);
+            assert_valid(&network, &flow, 1, node_count);
+            assert!(!has_negative_cycle(&network, &flow.flows));
+
+            let half = network.min_cost_flow(1, node_count, flow.value / 2).unwrap();
+            assert_valid(&network, &half, 1, node_count);
+            assert!(!has_negative_cycle(&network, &half.flows));
+        }
+    }
+}
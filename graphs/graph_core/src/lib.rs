@@ -0,0 +1,154 @@
+pub mod scc;
+pub mod shortest_paths;
+pub mod traversal;
+pub mod union_find;
+
+/// Graph with nodes numbered from 1 to `node_count`
+pub trait Graph {
+    fn node_count(&self) -> usize;
+
+    /// Adjacent node ids of `node_id`, parallel edges are repeated
+    fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_;
+}
+
+pub trait WeightedGraph: Graph {
+    /// `(adjacent node id, edge weight)` pairs of `node_id`
+    fn weighted_neighbours(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_;
+}
+
+/// Component id with the number of its nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentSize {
+    pub component_id: usize,
+    pub size: usize,
+}
+
+/// Partition of graph nodes into components, both node and component ids start from 1
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Components {
+    // component id of every node, indexed by node_id - 1
+    membership: Vec<usize>,
+    // number of nodes in every component, indexed by component id - 1
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    /// `membership` is the component id of every node, indexed by node_id - 1;
+    /// component ids should cover 1..=count without gaps
+    pub fn from_membership(membership: Vec<usize>) -> Self {
+        let mut sizes = vec![0; membership.iter().max().copied().unwrap_or(0)];
+        for component_id in membership.iter() {
+            assert!(*component_id > 0, "Component ids start from 1");
+            sizes[component_id - 1] += 1;
+        }
+        assert!(sizes.iter().all(|size| *size > 0), "Component ids should have no gaps");
+        Components { membership, sizes }
+    }
+
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn component_of(&self, node_id: usize) -> usize {
+        self.membership[node_id - 1]
+    }
+
+    pub fn size_of(&self, component_id: usize) -> usize {
+        self.sizes[component_id - 1]
+    }
+
+    /// Component id of every node, indexed by node_id - 1
+    pub fn membership(&self) -> &[usize] {
+        &self.membership
+    }
+
+    /// Size of every component, indexed by component id - 1
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Up to `k` largest components, largest first and ties by ascending component id.
+    /// Selection is linear in the number of components plus sorting the k selected ones.
+    pub fn largest(&self, k: usize) -> Vec<ComponentSize> {
+        let mut candidates = self.sizes.iter()
+            .enumerate()
+            .map(|(index, size)| ComponentSize { component_id: index + 1, size: *size })
+            .collect::<Vec<ComponentSize>>();
+        let by_size_desc = |a: &ComponentSize, b: &ComponentSize| {
+            b.size.cmp(&a.size).then(a.component_id.cmp(&b.component_id))
+        };
+        if k < candidates.len() {
+            if k > 0 {
+                candidates.select_nth_unstable_by(k - 1, by_size_desc);
+            }
+            candidates.truncate(k);
+        }
+        candidates.sort_unstable_by(by_size_desc);
+        candidates
+    }
+
+    /// Sizes of the `k` largest components, padded with `fill` when there are fewer components
+    pub fn largest_sizes_padded(&self, k: usize, fill: usize) -> Vec<usize> {
+        let mut sizes = self.largest(k).into_iter()
+            .map(|component| component.size)
+            .collect::<Vec<usize>>();
+        sizes.resize(k, fill);
+        sizes
+    }
+
+    /// Node ids of every component in ascending order, indexed by component id - 1
+    pub fn members(&self) -> Vec<Vec<usize>> {
+        let mut members: Vec<Vec<usize>> = self.sizes.iter()
+            .map(|size| Vec::with_capacity(*size))
+            .collect();
+        for (index, component_id) in self.membership.iter().enumerate() {
+            members[component_id - 1].push(index + 1);
+        }
+        members
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{Components, ComponentSize, Graph};
+
+    // adjacency lists indexed by node_id - 1
+    pub(crate) struct AdjacencyLists(pub Vec<Vec<usize>>);
+
+    impl Graph for AdjacencyLists {
+        fn node_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+            self.0[node_id - 1].iter().copied()
+        }
+    }
+
+    #[test]
+    fn components_from_membership() {
+        let components = Components::from_membership(vec![2, 1, 2, 3]);
+
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.sizes(), &[1, 2, 1]);
+        assert_eq!(components.members(), vec![vec![2], vec![1, 3], vec![4]]);
+    }
+
+    #[test]
+    fn largest_components() {
+        let components = Components::from_membership(vec![1, 2, 2, 3, 3, 3, 4, 4, 5]);
+        let size = |component_id, size| ComponentSize { component_id, size };
+
+        assert_eq!(components.largest(3), vec![size(3, 3), size(2, 2), size(4, 2)]);
+        assert_eq!(components.largest(7).len(), 5);
+        assert_eq!(components.largest(0), vec![]);
+        assert_eq!(components.largest_sizes_padded(7, 0), vec![3, 2, 2, 1, 1, 0, 0]);
+        assert_eq!(components.largest_sizes_padded(2, 0), vec![3, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_gaps_in_component_ids() {
+        Components::from_membership(vec![1, 3]);
+    }
+}
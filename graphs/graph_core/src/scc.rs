@@ -0,0 +1,112 @@
+use crate::{Components, Graph};
+
+/// Tarjan's strongly connected components with an explicit call stack.
+/// Components are numbered in reverse topological order: component 1 is a sink
+/// and every edge between components goes from a higher id to a lower one.
+pub fn strongly_connected_components<G: Graph>(graph: &G) -> Components {
+    let node_count = graph.node_count();
+    let mut state = TarjanState {
+        discovered: vec![0; node_count],
+        low_link: vec![0; node_count],
+        on_stack: vec![false; node_count],
+        component_stack: Vec::new(),
+        discovery_counter: 0,
+    };
+    let mut membership = vec![0_usize; node_count];
+    let mut component_counter = 0_usize;
+
+    for root in 1..=node_count {
+        if state.discovered[root - 1] != 0 {
+            continue;
+        }
+        state.visit(root);
+        let mut call_stack = vec![(root, graph.neighbours(root))];
+
+        while let Some((node_id, neighbours)) = call_stack.last_mut() {
+            let node_id = *node_id;
+            match neighbours.next() {
+                Some(adjacent_node_id) if state.discovered[adjacent_node_id - 1] == 0 => {
+                    state.visit(adjacent_node_id);
+                    call_stack.push((adjacent_node_id, graph.neighbours(adjacent_node_id)));
+                }
+                Some(adjacent_node_id) => {
+                    if state.on_stack[adjacent_node_id - 1] {
+                        state.lower_link(node_id, state.discovered[adjacent_node_id - 1]);
+                    }
+                }
+                None => {
+                    call_stack.pop();
+                    if let Some((parent_id, _)) = call_stack.last() {
+                        state.lower_link(*parent_id, state.low_link[node_id - 1]);
+                    }
+                    if state.low_link[node_id - 1] == state.discovered[node_id - 1] {
+                        // node is the root of a component, everything above it on the stack belongs to it
+                        component_counter += 1;
+                        loop {
+                            let member = state.component_stack.pop().unwrap();
+                            state.on_stack[member - 1] = false;
+                            membership[member - 1] = component_counter;
+                            if member == node_id {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Components::from_membership(membership)
+}
+
+struct TarjanState {
+    // discovery index starting from 1, 0 for unexplored nodes
+    discovered: Vec<usize>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    component_stack: Vec<usize>,
+    discovery_counter: usize,
+}
+
+impl TarjanState {
+    fn visit(&mut self, node_id: usize) {
+        self.discovery_counter += 1;
+        self.discovered[node_id - 1] = self.discovery_counter;
+        self.low_link[node_id - 1] = self.discovery_counter;
+        self.on_stack[node_id - 1] = true;
+        self.component_stack.push(node_id);
+    }
+
+    fn lower_link(&mut self, node_id: usize, candidate: usize) {
+        self.low_link[node_id - 1] = self.low_link[node_id - 1].min(candidate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scc::strongly_connected_components;
+    use crate::tests::AdjacencyLists;
+
+    #[test]
+    fn components_in_reverse_topological_order() {
+        // 1 <-> 2 -> 3 <-> 4 -> 5, 6 isolated
+        let graph = AdjacencyLists(vec![vec![2], vec![1, 3], vec![4], vec![3, 5], vec![], vec![]]);
+        let components = strongly_connected_components(&graph);
+
+        assert_eq!(components.count(), 4);
+        assert_eq!(components.component_of(1), components.component_of(2));
+        assert_eq!(components.component_of(3), components.component_of(4));
+        assert!(components.component_of(5) < components.component_of(3));
+        assert!(components.component_of(3) < components.component_of(1));
+        assert_eq!(components.size_of(components.component_of(6)), 1);
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow() {
+        let node_count = 200_000;
+        let mut lists: Vec<Vec<usize>> = (1..node_count).map(|node_id| vec![node_id + 1]).collect();
+        lists.push(vec![1]);
+        let components = strongly_connected_components(&AdjacencyLists(lists));
+
+        assert_eq!(components.sizes(), &[node_count]);
+    }
+}
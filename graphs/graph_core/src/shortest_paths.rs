@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use crate::WeightedGraph;
+
+/// Shortest path lengths from `source` for edge weights 0 and 1, indexed by node_id - 1;
+/// None for unreachable nodes. Zero-weight edges go to the front of the deque.
+pub fn zero_one_breadth_first<G: WeightedGraph>(graph: &G, source: usize) -> Vec<Option<usize>> {
+    let mut distances: Vec<Option<usize>> = vec![None; graph.node_count()];
+    let mut settled = vec![false; graph.node_count()];
+    let mut deque = VecDeque::from([source]);
+    distances[source - 1] = Some(0);
+    while let Some(node_id) = deque.pop_front() {
+        if settled[node_id - 1] {
+            continue;
+        }
+        settled[node_id - 1] = true;
+        let distance = distances[node_id - 1].unwrap();
+        for (adjacent_node_id, weight) in graph.weighted_neighbours(node_id) {
+            assert!(weight <= 1, "0-1 breadth-first search needs weights 0 or 1, got {}", weight);
+            if distances[adjacent_node_id - 1].is_none_or(|current| distance + weight < current) {
+                distances[adjacent_node_id - 1] = Some(distance + weight);
+                if weight == 0 {
+                    deque.push_front(adjacent_node_id);
+                } else {
+                    deque.push_back(adjacent_node_id);
+                }
+            }
+        }
+    }
+    distances
+}
+
+/// Dial's Dijkstra with a ring of `max_weight + 1` distance buckets, O(m + n * max_weight)
+/// for weights up to `max_weight`; indexed by node_id - 1, None for unreachable nodes
+pub fn dial<G: WeightedGraph>(graph: &G, source: usize, max_weight: usize) -> Vec<Option<usize>> {
+    let mut distances: Vec<Option<usize>> = vec![None; graph.node_count()];
+    let mut settled = vec![false; graph.node_count()];
+    // a bucket holds nodes at distances congruent modulo the ring size,
+    // but only the current distance can be present when it is scanned
+    let ring_size = max_weight + 1;
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); ring_size];
+    let mut pending = 1;
+    buckets[0].push(source);
+    distances[source - 1] = Some(0);
+    let mut distance = 0;
+    while pending > 0 {
+        let bucket = distance % ring_size;
+        while let Some(node_id) = buckets[bucket].pop() {
+            pending -= 1;
+            // stale entries were superseded by a shorter distance
+            if settled[node_id - 1] || distances[node_id - 1] != Some(distance) {
+                continue;
+            }
+            settled[node_id - 1] = true;
+            for (adjacent_node_id, weight) in graph.weighted_neighbours(node_id) {
+                assert!(weight <= max_weight, "Edge weight {} exceeds {}", weight, max_weight);
+                if distances[adjacent_node_id - 1].is_none_or(|current| distance + weight < current) {
+                    distances[adjacent_node_id - 1] = Some(distance + weight);
+                    buckets[(distance + weight) % ring_size].push(adjacent_node_id);
+                    pending += 1;
+                }
+            }
+        }
+        distance += 1;
+    }
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Graph, WeightedGraph};
+    use crate::shortest_paths::{dial, zero_one_breadth_first};
+
+    // (adjacent node id, weight) lists indexed by node_id - 1
+    struct WeightedAdjacencyLists(Vec<Vec<(usize, usize)>>);
+
+    impl Graph for WeightedAdjacencyLists {
+        fn node_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+            self.0[node_id - 1].iter().map(|(adjacent_node_id, _)| *adjacent_node_id)
+        }
+    }
+
+    impl WeightedGraph for WeightedAdjacencyLists {
+        fn weighted_neighbours(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+            self.0[node_id - 1].iter().copied()
+        }
+    }
+
+    #[test]
+    fn zero_one_distances() {
+        let graph = WeightedAdjacencyLists(vec![
+            vec![(2, 1), (3, 0)], vec![(4, 0)], vec![(2, 0), (4, 1)], vec![], vec![(1, 0)]
+        ]);
+
+        assert_eq!(zero_one_breadth_first(&graph, 1), vec![Some(0), Some(0), Some(0), Some(0), None]);
+        assert_eq!(zero_one_breadth_first(&graph, 5), vec![Some(0), Some(0), Some(0), Some(0), Some(0)]);
+        assert_eq!(dial(&graph, 1, 1), zero_one_breadth_first(&graph, 1));
+    }
+
+    #[test]
+    fn dial_distances() {
+        let graph = WeightedAdjacencyLists(vec![
+            vec![(2, 7), (3, 2)], vec![(5, 1)], vec![(2, 3), (4, 5)], vec![(5, 0)], vec![], vec![(1, 1)]
+        ]);
+
+        assert_eq!(dial(&graph, 1, 7), vec![Some(0), Some(5), Some(2), Some(7), Some(6), None]);
+        assert_eq!(dial(&graph, 6, 10), vec![Some(1), Some(6), Some(3), Some(8), Some(7), Some(0)]);
+    }
+}
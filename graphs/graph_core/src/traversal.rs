@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+
+use crate::Graph;
+
+/// Node ids reachable from `source` in breadth-first order
+pub fn breadth_first<G: Graph>(graph: &G, source: usize) -> Vec<usize> {
+    let mut explored = vec![false; graph.node_count()];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([source]);
+    explored[source - 1] = true;
+    while let Some(node_id) = queue.pop_front() {
+        order.push(node_id);
+        for adjacent_node_id in graph.neighbours(node_id) {
+            if !explored[adjacent_node_id - 1] {
+                explored[adjacent_node_id - 1] = true;
+                queue.push_back(adjacent_node_id);
+            }
+        }
+    }
+    order
+}
+
+/// Number of edges on a shortest path from `source` to every node, indexed by node_id - 1;
+/// None for unreachable nodes
+pub fn breadth_first_distances<G: Graph>(graph: &G, source: usize) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.node_count()];
+    let mut queue = VecDeque::from([source]);
+    distances[source - 1] = Some(0);
+    while let Some(node_id) = queue.pop_front() {
+        let next_distance = distances[node_id - 1].map(|distance| distance + 1);
+        for adjacent_node_id in graph.neighbours(node_id) {
+            if distances[adjacent_node_id - 1].is_none() {
+                distances[adjacent_node_id - 1] = next_distance;
+                queue.push_back(adjacent_node_id);
+            }
+        }
+    }
+    distances
+}
+
+/// Node ids reachable from `source` in depth-first preorder, the same order a recursive
+/// search would give, but with an explicit stack so deep graphs can't overflow
+pub fn depth_first<G: Graph>(graph: &G, source: usize) -> Vec<usize> {
+    let mut explored = vec![false; graph.node_count()];
+    let mut order = vec![source];
+    let mut stack = vec![graph.neighbours(source)];
+    explored[source - 1] = true;
+    while let Some(neighbours) = stack.last_mut() {
+        match neighbours.next() {
+            Some(adjacent_node_id) if !explored[adjacent_node_id - 1] => {
+                explored[adjacent_node_id - 1] = true;
+                order.push(adjacent_node_id);
+                stack.push(graph.neighbours(adjacent_node_id));
+            }
+            Some(_) => {}
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::AdjacencyLists;
+    use crate::traversal::{breadth_first, breadth_first_distances, depth_first};
+
+    #[test]
+    fn traversal_orders() {
+        let graph = AdjacencyLists(vec![vec![2, 3], vec![4], vec![4, 5], vec![1], vec![], vec![1]]);
+
+        assert_eq!(breadth_first(&graph, 1), vec![1, 2, 3, 4, 5]);
+        assert_eq!(depth_first(&graph, 1), vec![1, 2, 4, 3, 5]);
+        assert_eq!(depth_first(&graph, 5), vec![5]);
+        assert_eq!(breadth_first(&graph, 6), vec![6, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn distances_by_edge_count() {
+        let graph = AdjacencyLists(vec![vec![2, 3], vec![4], vec![4, 5], vec![1], vec![], vec![1]]);
+
+        assert_eq!(breadth_first_distances(&graph, 1), vec![Some(0), Some(1), Some(1), Some(2), Some(2), None]);
+        assert_eq!(breadth_first_distances(&graph, 5), vec![None, None, None, None, Some(0), None]);
+    }
+}
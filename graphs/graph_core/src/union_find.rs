@@ -0,0 +1,148 @@
+use crate::{Components, Graph};
+
+/// Disjoint sets of node ids starting from 1, union by size with path halving
+#[derive(Debug, Default, Clone)]
+pub struct UnionFind {
+    // parent of every node, indexed by node_id - 1; roots are their own parents
+    parents: Vec<usize>,
+    // set size of every root, indexed by node_id - 1
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl UnionFind {
+    /// `count` singleton sets, ids from 1 to `count`
+    pub fn new(count: usize) -> Self {
+        let mut sets = UnionFind::default();
+        sets.extend_to(count);
+        sets
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Adds singleton sets for ids up to `count`
+    pub fn extend_to(&mut self, count: usize) {
+        for node_id in (self.parents.len() + 1)..=count {
+            self.parents.push(node_id);
+            self.sizes.push(1);
+            self.set_count += 1;
+        }
+    }
+
+    /// Representative id of the set holding `node_id`
+    pub fn find(&mut self, node_id: usize) -> usize {
+        let mut current = node_id;
+        while self.parents[current - 1] != current {
+            let grandparent = self.parents[self.parents[current - 1] - 1];
+            self.parents[current - 1] = grandparent;
+            current = grandparent;
+        }
+        current
+    }
+
+    /// False if both were already in the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a - 1] < self.sizes[b - 1] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b - 1] = a;
+        self.sizes[a - 1] += self.sizes[b - 1];
+        self.set_count -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size_of_set(&mut self, node_id: usize) -> usize {
+        let root = self.find(node_id);
+        self.sizes[root - 1]
+    }
+
+    /// Sets as components, numbered by their smallest node id
+    pub fn components(&mut self) -> Components {
+        let mut component_ids = vec![0; self.parents.len()];
+        let mut count = 0;
+        let membership = (1..=self.parents.len())
+            .map(|node_id| {
+                let root = self.find(node_id);
+                if component_ids[root - 1] == 0 {
+                    count += 1;
+                    component_ids[root - 1] = count;
+                }
+                component_ids[root - 1]
+            })
+            .collect();
+        Components::from_membership(membership)
+    }
+}
+
+/// Components of the graph with every edge taken as undirected, numbered by their
+/// smallest node id; for a directed graph these are its weakly connected components
+pub fn connected_components<G: Graph>(graph: &G) -> Components {
+    let mut sets = UnionFind::new(graph.node_count());
+    for node_id in 1..=graph.node_count() {
+        for adjacent_node_id in graph.neighbours(node_id) {
+            sets.union(node_id, adjacent_node_id);
+        }
+    }
+    sets.components()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::AdjacencyLists;
+    use crate::union_find::{connected_components, UnionFind};
+
+    #[test]
+    fn merges_sets() {
+        let mut sets = UnionFind::new(6);
+
+        assert!(sets.union(1, 4));
+        assert!(sets.union(4, 5));
+        assert!(!sets.union(5, 1));
+        assert!(sets.union(6, 3));
+        assert!(sets.same_set(1, 5));
+        assert!(!sets.same_set(1, 3));
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.size_of_set(4), 3);
+        assert_eq!(sets.components().membership(), &[1, 2, 3, 1, 1, 3]);
+    }
+
+    #[test]
+    fn extends_with_singletons() {
+        let mut sets = UnionFind::default();
+        sets.extend_to(2);
+        sets.union(1, 2);
+        sets.extend_to(4);
+
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.components().sizes(), &[2, 1, 1]);
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        // 1 -> 3 <- 2, 4 -> 5, 6 alone
+        let graph = AdjacencyLists(vec![vec![3], vec![3], vec![], vec![5], vec![], vec![]]);
+        let components = connected_components(&graph);
+
+        assert_eq!(components.membership(), &[1, 1, 1, 2, 2, 3]);
+        assert_eq!(components.sizes(), &[3, 2, 1]);
+    }
+}
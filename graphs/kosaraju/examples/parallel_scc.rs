@@ -0,0 +1,47 @@
+//! Benchmark of the parallel SCC decomposition on a generated graph with 5M edges:
+//! cargo run --release --example parallel_scc [threads]
+
+use std::time::Instant;
+
+use kosaraju::csr::CsrGraph;
+use utils::XorShift64;
+
+const NODE_COUNT: usize = 1_000_000;
+const EDGE_COUNT: usize = 5_000_000;
+
+fn main() {
+    let threads = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse::<usize>().expect("Thread count should be a number"))
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |threads| threads.get()));
+
+    // mostly forward edges with some back edges: one giant component, a long tail of small ones
+    let mut rng = XorShift64::new(5);
+    let edges = (0..EDGE_COUNT)
+        .map(|_| {
+            let from = 1 + rng.below(NODE_COUNT);
+            let to = 1 + rng.below(NODE_COUNT);
+            if rng.below(10) < 7 { (from.min(to), from.max(to)) } else { (from, to) }
+        })
+        .collect::<Vec<(usize, usize)>>();
+    let graph = CsrGraph::from_edges(NODE_COUNT, &edges);
+
+    let started = Instant::now();
+    let sequential = graph.strongly_connected_components();
+    let sequential_time = started.elapsed();
+
+    let started = Instant::now();
+    let parallel = graph.parallel_strongly_connected_components(threads);
+    let parallel_time = started.elapsed();
+
+    let mut expected = sequential.members();
+    let mut actual = parallel.members();
+    expected.sort_unstable();
+    actual.sort_unstable();
+    assert_eq!(expected, actual, "parallel decomposition differs from Kosaraju");
+
+    println!("{} nodes, {} edges, {} components, largest {:?}",
+             NODE_COUNT, EDGE_COUNT, sequential.count(), sequential.largest_sizes_padded(3, 0));
+    println!("sequential Kosaraju: {:?}", sequential_time);
+    println!("parallel FW-BW on {} threads: {:?}", threads, parallel_time);
+}
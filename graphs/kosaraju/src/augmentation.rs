@@ -0,0 +1,168 @@
+use crate::{DirectedGraph, condensation::Condensation};
+
+impl DirectedGraph {
+    /// Minimum set of new edges that makes the graph strongly connected (Eswaran-Tarjan).
+    /// Edges connect the smallest node ids of the condensation components they join.
+    pub fn strong_connectivity_augmentation(&self) -> Vec<(usize, usize)> {
+        let condensation = self.condensation();
+        let representatives = condensation.components.members()
+            .into_iter()
+            .map(|members| members[0])
+            .collect::<Vec<usize>>();
+        condensation.augmentation()
+            .into_iter()
+            .map(|(from, to)| (representatives[from - 1], representatives[to - 1]))
+            .collect()
+    }
+}
+
+impl Condensation {
+    /// Eswaran-Tarjan augmentation in component ids: with `s` sources, `t` sinks and `q`
+    /// isolated components (counted in neither) it adds `max(s, t) + q` edges
+    pub fn augmentation(&self) -> Vec<(usize, usize)> {
+        let count = self.components.count();
+        if count <= 1 {
+            return Vec::new();
+        }
+        let isolated = self.sources.iter()
+            .filter(|component_id| self.sinks.contains(component_id))
+            .copied()
+            .collect::<Vec<usize>>();
+        let sources = self.sources.iter().filter(|id| !isolated.contains(id)).copied().collect::<Vec<usize>>();
+        let sinks = self.sinks.iter().filter(|id| !isolated.contains(id)).copied().collect::<Vec<usize>>();
+
+        if sources.len() <= sinks.len() {
+            augment(&self.graph, &sources, &sinks, &isolated)
+        } else {
+            // same construction on the reversed graph, with the new edges flipped back
+            augment(&self.graph.reversed(), &sinks, &sources, &isolated)
+                .into_iter()
+                .map(|(from, to)| (to, from))
+                .collect()
+        }
+    }
+}
+
+// expects no more sources than sinks
+fn augment(dag: &DirectedGraph, sources: &[usize], sinks: &[usize], isolated: &[usize]) -> Vec<(usize, usize)> {
+    if sources.is_empty() {
+        // only isolated components, join them in a ring
+        return (0..isolated.len())
+            .map(|index| (isolated[index], isolated[(index + 1) % isolated.len()]))
+            .collect();
+    }
+
+    // greedily pair sources with sinks they reach; marks stay between searches,
+    // which guarantees that unpaired sources and sinks are connected to paired ones
+    let mut is_sink = vec![false; dag.node_count()];
+    sinks.iter().for_each(|sink| is_sink[sink - 1] = true);
+    let mut marked = vec![false; dag.node_count()];
+    let mut matched_sources = Vec::new();
+    let mut matched_sinks = Vec::new();
+    let mut unmatched_sources = Vec::new();
+    for source in sources {
+        let mut stack = vec![*source];
+        let mut found = None;
+        while let Some(node_id) = stack.pop() {
+            if marked[node_id - 1] {
+                continue;
+            }
+            marked[node_id - 1] = true;
+            if is_sink[node_id - 1] {
+                found = Some(node_id);
+                break;
+            }
+            stack.extend(dag.nodes[node_id - 1].adjacent_node_ids.iter().rev());
+        }
+        match found {
+            Some(sink) => {
+                matched_sources.push(*source);
+                matched_sinks.push(sink);
+            }
+            None => unmatched_sources.push(*source),
+        }
+    }
+    let unmatched_sinks = sinks.iter()
+        .filter(|sink| !matched_sinks.contains(sink))
+        .copied()
+        .collect::<Vec<usize>>();
+
+    let pairs = matched_sources.len();
+    let mut edges = Vec::new();
+    // chain the matched pairs: t1 -> s2, ..., t(p-1) -> sp
+    for index in 1..pairs {
+        edges.push((matched_sinks[index - 1], matched_sources[index]));
+    }
+    // pair the remaining sources with as many remaining sinks
+    for (sink, source) in unmatched_sinks.iter().zip(unmatched_sources.iter()) {
+        edges.push((*sink, *source));
+    }
+    // close the ring through the leftover sinks and the isolated components
+    let mut ring = vec![matched_sinks[pairs - 1]];
+    ring.extend(unmatched_sinks.iter().skip(unmatched_sources.len()));
+    ring.extend(isolated.iter());
+    for index in 1..ring.len() {
+        edges.push((ring[index - 1], ring[index]));
+    }
+    edges.push((ring[ring.len() - 1], matched_sources[0]));
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::XorShift64;
+
+    use crate::DirectedGraph;
+
+    fn assert_augments(graph: &DirectedGraph) {
+        let condensation = graph.condensation();
+        let edges = graph.strong_connectivity_augmentation();
+
+        // isolated components count both as sources and sinks
+        let expected = if condensation.components.count() <= 1 {
+            0
+        } else {
+            condensation.sources.len().max(condensation.sinks.len())
+        };
+        assert_eq!(edges.len(), expected);
+
+        let mut augmented = graph.clone();
+        edges.iter().for_each(|(from, to)| augmented.add_edge(*from, *to));
+        assert_eq!(augmented.strongly_connected_components().count(), 1);
+    }
+
+    #[test]
+    fn connects_sources_sinks_and_isolated_components() {
+        // 1 -> 2 -> 3, 1 -> 4, 5 -> 4, 6 isolated, 7 <-> 8
+        let mut graph = DirectedGraph::with_node_count(6);
+        for (from, to) in [(1, 2), (2, 3), (1, 4), (5, 4), (7, 8), (8, 7)] {
+            graph.add_edge(from, to);
+        }
+
+        assert_augments(&graph);
+        assert_eq!(graph.strong_connectivity_augmentation().len(), 4);
+    }
+
+    #[test]
+    fn strongly_connected_graph_needs_nothing() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+
+        assert_eq!(graph.strong_connectivity_augmentation(), vec![]);
+        assert_eq!(DirectedGraph::with_node_count(3).strong_connectivity_augmentation().len(), 3);
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = XorShift64::new(7);
+        for _ in 0..300 {
+            let node_count = 1 + rng.below(15);
+            let mut graph = DirectedGraph::with_node_count(node_count);
+            for _ in 0..rng.below(2 * node_count) {
+                graph.add_edge(1 + rng.below(node_count), 1 + rng.below(node_count));
+            }
+            assert_augments(&graph);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{DirectedGraph, csr::CsrGraph};
+
+#[derive(Debug, Clone)]
+pub struct PageRankConfig {
+    /// probability of following an edge instead of teleporting
+    pub damping: f64,
+    /// iteration stops once scores change by less than this in total (L1 norm)
+    pub tolerance: f64,
+    pub max_iterations: usize,
+    /// teleport weight of every node indexed by node_id - 1, normalised to sum 1;
+    /// uniform when absent, personalized PageRank otherwise
+    pub teleport: Option<Vec<f64>>,
+}
+
+impl Default for PageRankConfig {
+    fn default() -> Self {
+        PageRankConfig { damping: 0.85, tolerance: 1e-10, max_iterations: 100, teleport: None }
+    }
+}
+
+/// Score of every node, indexed by node_id - 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scores {
+    scores: Vec<f64>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+impl Scores {
+    pub fn score(&self, node_id: usize) -> f64 {
+        self.scores[node_id - 1]
+    }
+
+    pub fn scores(&self) -> &[f64] {
+        &self.scores
+    }
+
+    /// `(node id, score)` pairs, highest score first and ties by ascending node id
+    pub fn ranked(&self) -> Vec<(usize, f64)> {
+        let mut ranked = self.scores.iter()
+            .enumerate()
+            .map(|(index, score)| (index + 1, *score))
+            .collect::<Vec<(usize, f64)>>();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+}
+
+/// Hub and authority scores, both scaled to sum 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hits {
+    pub hubs: Scores,
+    pub authorities: Scores,
+}
+
+impl DirectedGraph {
+    /// Power iteration, scores sum to 1. The rank of nodes without outgoing edges
+    /// is spread by the teleport distribution, so no rank leaks out of the graph.
+    pub fn page_rank(&self, config: &PageRankConfig) -> Scores {
+        let graph = CsrGraph::from(self);
+        let node_count = graph.node_count();
+        let teleport = match &config.teleport {
+            Some(weights) => {
+                assert_eq!(weights.len(), node_count, "Teleport weight is needed for every node");
+                let total: f64 = weights.iter().sum();
+                assert!(total > 0.0, "Teleport weights should not all be zero");
+                weights.iter().map(|weight| weight / total).collect()
+            }
+            None => vec![1.0 / node_count as f64; node_count],
+        };
+
+        let mut ranks = teleport.clone();
+        let mut next = vec![0.0; node_count];
+        let mut iterations = 0;
+        let mut converged = node_count == 0;
+        while !converged && iterations < config.max_iterations {
+            iterations += 1;
+            let mut dangling = 0.0;
+            next.iter_mut().for_each(|rank| *rank = 0.0);
+            for node_id in 1..=node_count {
+                let targets = graph.adjacent_node_ids(node_id);
+                if targets.is_empty() {
+                    dangling += ranks[node_id - 1];
+                    continue;
+                }
+                let share = ranks[node_id - 1] / targets.len() as f64;
+                for target in targets {
+                    next[target - 1] += share;
+                }
+            }
+            for (rank, teleport) in next.iter_mut().zip(teleport.iter()) {
+                *rank = config.damping * (*rank + dangling * teleport) + (1.0 - config.damping) * teleport;
+            }
+            converged = l1_distance(&ranks, &next) < config.tolerance;
+            std::mem::swap(&mut ranks, &mut next);
+        }
+        Scores { scores: ranks, iterations, converged }
+    }
+
+    /// Kleinberg's hubs and authorities: a good hub points to good authorities
+    /// and a good authority is pointed to by good hubs
+    pub fn hits(&self, tolerance: f64, max_iterations: usize) -> Hits {
+        let graph = CsrGraph::from(self);
+        let node_count = graph.node_count();
+        let mut hubs = vec![1.0 / node_count as f64; node_count];
+        let mut authorities = hubs.clone();
+        let mut iterations = 0;
+        let mut converged = node_count == 0;
+        while !converged && iterations < max_iterations {
+            iterations += 1;
+            let mut next_authorities = vec![0.0; node_count];
+            for node_id in 1..=node_count {
+                for target in graph.adjacent_node_ids(node_id) {
+                    next_authorities[target - 1] += hubs[node_id - 1];
+                }
+            }
+            normalise(&mut next_authorities);
+            let mut next_hubs = (1..=node_count)
+                .map(|node_id| graph.adjacent_node_ids(node_id).iter().map(|target| next_authorities[target - 1]).sum())
+                .collect::<Vec<f64>>();
+            normalise(&mut next_hubs);
+
+            converged = l1_distance(&hubs, &next_hubs) + l1_distance(&authorities, &next_authorities) < tolerance;
+            hubs = next_hubs;
+            authorities = next_authorities;
+        }
+        Hits {
+            hubs: Scores { scores: hubs, iterations, converged },
+            authorities: Scores { scores: authorities, iterations, converged },
+        }
+    }
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum()
+}
+
+fn normalise(scores: &mut [f64]) {
+    let total: f64 = scores.iter().sum();
+    if total > 0.0 {
+        scores.iter_mut().for_each(|score| *score /= total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DirectedGraph, centrality::PageRankConfig};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn page_rank_with_dangling_node() {
+        // 1 -> 2, 1 -> 3, 2 -> 3, 3 -> 1, 4 -> 3; 5 has no outgoing edges
+        let mut graph = DirectedGraph::with_node_count(5);
+        for (from, to) in [(1, 2), (1, 3), (2, 3), (3, 1), (4, 3), (4, 5)] {
+            graph.add_edge(from, to);
+        }
+        let scores = graph.page_rank(&PageRankConfig::default());
+
+        assert!(scores.converged);
+        assert_close(scores.scores().iter().sum(), 1.0);
+        let ranked = scores.ranked().into_iter().map(|(node_id, _)| node_id).collect::<Vec<usize>>();
+        assert_eq!(ranked[..3], [3, 1, 2]);
+        assert!(scores.score(5) > scores.score(4));
+    }
+
+    #[test]
+    fn symmetric_cycle_has_uniform_rank() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+        let scores = graph.page_rank(&PageRankConfig::default());
+
+        scores.scores().iter().for_each(|score| assert_close(*score, 1.0 / 3.0));
+    }
+
+    #[test]
+    fn personalized_page_rank_favours_teleport_targets() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 3);
+        let config = PageRankConfig { teleport: Some(vec![1.0, 0.0, 0.0, 0.0]), ..PageRankConfig::default() };
+        let scores = graph.page_rank(&config);
+
+        assert_close(scores.score(3) + scores.score(4), 0.0);
+        assert_close(scores.score(1), 1.0 / 1.85);
+    }
+
+    #[test]
+    fn hubs_and_authorities() {
+        // 1 and 2 point to 3 and 4, 5 points to 4 only
+        let mut graph = DirectedGraph::default();
+        for (from, to) in [(1, 3), (1, 4), (2, 3), (2, 4), (5, 4)] {
+            graph.add_edge(from, to);
+        }
+        let hits = graph.hits(1e-10, 1000);
+
+        assert!(hits.hubs.converged);
+        assert_eq!(hits.authorities.ranked()[0].0, 4);
+        assert_eq!(hits.hubs.ranked().iter().map(|(node_id, _)| *node_id).take(3).collect::<Vec<_>>(), vec![1, 2, 5]);
+        assert_close(hits.hubs.score(3), 0.0);
+        assert_close(hits.authorities.scores().iter().sum(), 1.0);
+    }
+}
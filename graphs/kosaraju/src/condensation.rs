@@ -0,0 +1,107 @@
+use crate::{Components, DirectedGraph};
+
+/// Component graph of a [`DirectedGraph`]: one node per strongly connected component
+/// and one edge for every pair of components connected in the source graph
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    /// node ids of the condensed graph are the component ids
+    pub graph: DirectedGraph,
+    /// membership of the source graph nodes
+    pub components: Components,
+    /// components without incoming edges, ascending
+    pub sources: Vec<usize>,
+    /// components without outgoing edges, ascending
+    pub sinks: Vec<usize>,
+}
+
+impl DirectedGraph {
+    pub fn condensation(&self) -> Condensation {
+        let components = self.strongly_connected_components();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); components.count()];
+        for node in self.nodes.iter() {
+            let from = components.component_of(node.meta.node_id);
+            for adjacent_node_id in &node.adjacent_node_ids {
+                let to = components.component_of(*adjacent_node_id);
+                if from != to {
+                    successors[from - 1].push(to);
+                }
+            }
+        }
+
+        let mut graph = DirectedGraph::with_node_count(components.count());
+        let mut has_incoming = vec![false; components.count()];
+        for (index, targets) in successors.iter_mut().enumerate() {
+            targets.sort_unstable();
+            targets.dedup();
+            for to in targets.iter() {
+                graph.add_edge(index + 1, *to);
+                has_incoming[to - 1] = true;
+            }
+        }
+
+        let sources = (1..=components.count())
+            .filter(|component_id| !has_incoming[component_id - 1])
+            .collect();
+        let sinks = (1..=components.count())
+            .filter(|component_id| successors[component_id - 1].is_empty())
+            .collect();
+
+        Condensation { graph, components, sources, sinks }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::DirectedGraph;
+
+    #[test]
+    fn condensation_of_chained_cycles() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/3.txt").unwrap()));
+        let condensation = graph.condensation();
+
+        assert_eq!(condensation.components.count(), 4);
+        assert_eq!(condensation.graph.node_count(), 4);
+        let members = condensation.components.members();
+        let cycle_a = condensation.components.component_of(1);
+        let cycle_b = condensation.components.component_of(6);
+        let middle = condensation.components.component_of(4);
+        let leaf = condensation.components.component_of(5);
+        assert_eq!(members[cycle_a - 1], vec![1, 2, 3]);
+        assert_eq!(members[cycle_b - 1], vec![6, 7, 8]);
+        assert_eq!(members[middle - 1], vec![4]);
+
+        let mut sources = vec![cycle_a, cycle_b, leaf];
+        sources.sort();
+        assert_eq!(condensation.sources, sources);
+        assert_eq!(condensation.sinks, vec![middle]);
+    }
+
+    #[test]
+    fn edges_are_deduplicated_and_point_to_lower_ids() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(4, 4);
+
+        let condensation = graph.condensation();
+        assert_eq!(condensation.components.count(), 3);
+        let mut edge_count = 0;
+        for node in condensation.graph.nodes.iter() {
+            for to in &node.adjacent_node_ids {
+                assert!(node.meta.node_id > *to);
+                edge_count += 1;
+            }
+        }
+        assert_eq!(edge_count, 1);
+
+        // isolated component is both source and sink
+        let isolated = condensation.components.component_of(4);
+        assert!(condensation.sources.contains(&isolated));
+        assert!(condensation.sinks.contains(&isolated));
+    }
+}
@@ -0,0 +1,295 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use graph_core::{Graph, WeightedGraph};
+
+use crate::{
+    Components,
+    DirectedGraph,
+    topo::{self, Cycle},
+};
+
+/// Compressed sparse row graph: adjacency of all nodes packed into one targets array,
+/// node ids start from 1 as in [`DirectedGraph`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CsrGraph {
+    // adjacent node ids of node v are targets[offsets[v - 1]..offsets[v]]
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    // weight of every edge in targets, absent for unweighted graphs
+    weights: Option<Vec<usize>>,
+}
+
+impl CsrGraph {
+    pub fn from_edges(node_count: usize, edges: &[(usize, usize)]) -> Self {
+        let (offsets, slots) = Self::layout(node_count, edges.iter().map(|(from, _)| *from));
+        let mut targets = vec![0; edges.len()];
+        for ((_, to), slot) in edges.iter().zip(slots) {
+            assert!(*to >= 1 && *to <= node_count, "Node {} is out of range", to);
+            targets[slot] = *to;
+        }
+        CsrGraph { offsets, targets, weights: None }
+    }
+
+    /// Edges are `(from, to, weight)` triples
+    pub fn from_weighted_edges(node_count: usize, edges: &[(usize, usize, usize)]) -> Self {
+        let (offsets, slots) = Self::layout(node_count, edges.iter().map(|(from, _, _)| *from));
+        let mut targets = vec![0; edges.len()];
+        let mut weights = vec![0; edges.len()];
+        for ((_, to, weight), slot) in edges.iter().zip(slots) {
+            assert!(*to >= 1 && *to <= node_count, "Node {} is out of range", to);
+            targets[slot] = *to;
+            weights[slot] = *weight;
+        }
+        CsrGraph { offsets, targets, weights: Some(weights) }
+    }
+
+    pub fn from_graph<G: Graph>(graph: &G) -> Self {
+        let edges = (1..=graph.node_count())
+            .flat_map(|node_id| graph.neighbours(node_id).map(move |to| (node_id, to)))
+            .collect::<Vec<(usize, usize)>>();
+        Self::from_edges(graph.node_count(), &edges)
+    }
+
+    pub fn from_weighted_graph<G: WeightedGraph>(graph: &G) -> Self {
+        let edges = (1..=graph.node_count())
+            .flat_map(|node_id| graph.weighted_neighbours(node_id).map(move |(to, weight)| (node_id, to, weight)))
+            .collect::<Vec<(usize, usize, usize)>>();
+        Self::from_weighted_edges(graph.node_count(), &edges)
+    }
+
+    // counting sort by source node: returns offsets and the target slot of every edge,
+    // edges of the same node keep their input order
+    fn layout<I: Iterator<Item=usize> + Clone>(node_count: usize, sources: I) -> (Vec<usize>, Vec<usize>) {
+        let mut offsets = vec![0; node_count + 1];
+        for from in sources.clone() {
+            assert!(from >= 1 && from <= node_count, "Node {} is out of range", from);
+            offsets[from] += 1;
+        }
+        for index in 1..offsets.len() {
+            offsets[index] += offsets[index - 1];
+        }
+        let mut next_slot = offsets[..node_count].to_vec();
+        let slots = sources
+            .map(|from| {
+                next_slot[from - 1] += 1;
+                next_slot[from - 1] - 1
+            })
+            .collect();
+        (offsets, slots)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn adjacent_node_ids(&self, node_id: usize) -> &[usize] {
+        &self.targets[self.offsets[node_id - 1]..self.offsets[node_id]]
+    }
+
+    /// `(adjacent node id, weight)` pairs, unweighted edges weigh 1
+    pub fn weighted_edges(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        let range = self.offsets[node_id - 1]..self.offsets[node_id];
+        range.map(move |slot| (self.targets[slot], self.weights.as_ref().map_or(1, |weights| weights[slot])))
+    }
+
+    pub fn reversed(&self) -> CsrGraph {
+        let node_count = self.node_count();
+        let sources = (1..=node_count)
+            .flat_map(|node_id| self.adjacent_node_ids(node_id).iter().copied());
+        let (offsets, slots) = Self::layout(node_count, sources);
+        let mut targets = vec![0; self.targets.len()];
+        let mut weights = self.weights.as_ref().map(|_| vec![0; self.targets.len()]);
+        let mut slots = slots.into_iter();
+        for node_id in 1..=node_count {
+            for (from, weight) in self.weighted_edges(node_id) {
+                let slot = slots.next().unwrap();
+                debug_assert!(offsets[from - 1] <= slot && slot < offsets[from]);
+                targets[slot] = node_id;
+                if let Some(weights) = weights.as_mut() {
+                    weights[slot] = weight;
+                }
+            }
+        }
+        CsrGraph { offsets, targets, weights }
+    }
+
+    /// Kosaraju's algorithm with explicit stacks, components are numbered
+    /// in reverse topological order as in [`DirectedGraph::strongly_connected_components`]
+    pub fn strongly_connected_components(&self) -> Components {
+        let node_count = self.node_count();
+        let reversed = self.reversed();
+
+        // first pass: finishing order on the reversed graph
+        let mut explored = vec![false; node_count];
+        let mut finishing_order = Vec::with_capacity(node_count);
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for root in 1..=node_count {
+            if explored[root - 1] {
+                continue;
+            }
+            explored[root - 1] = true;
+            stack.push((root, 0));
+            while let Some((node_id, next)) = stack.last_mut() {
+                match reversed.adjacent_node_ids(*node_id).get(*next) {
+                    Some(&adjacent_node_id) => {
+                        *next += 1;
+                        if !explored[adjacent_node_id - 1] {
+                            explored[adjacent_node_id - 1] = true;
+                            stack.push((adjacent_node_id, 0));
+                        }
+                    }
+                    None => {
+                        finishing_order.push(*node_id);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        // second pass: collect components in decreasing finishing time
+        let mut membership = vec![0; node_count];
+        let mut scc_index = 0_usize;
+        let mut pending: Vec<usize> = Vec::new();
+        for root in finishing_order.into_iter().rev() {
+            if membership[root - 1] != 0 {
+                continue;
+            }
+            scc_index += 1;
+            membership[root - 1] = scc_index;
+            pending.push(root);
+            while let Some(node_id) = pending.pop() {
+                for adjacent_node_id in self.adjacent_node_ids(node_id) {
+                    if membership[adjacent_node_id - 1] == 0 {
+                        membership[adjacent_node_id - 1] = scc_index;
+                        pending.push(*adjacent_node_id);
+                    }
+                }
+            }
+        }
+        Components::from_membership(membership)
+    }
+
+    /// See [`DirectedGraph::topological_order`]
+    pub fn topological_order(&self) -> Result<Vec<usize>, Cycle> {
+        topo::depth_first_order(self.node_count(), |node_id, index| {
+            self.adjacent_node_ids(node_id).get(index).copied()
+        })
+    }
+
+    /// See [`DirectedGraph::topological_order_lexicographic`]
+    pub fn topological_order_lexicographic(&self) -> Result<Vec<usize>, Cycle> {
+        topo::lexicographic_order(self.node_count(), |node_id, index| {
+            self.adjacent_node_ids(node_id).get(index).copied()
+        })
+    }
+
+    /// Dijkstra's shortest path distances from the source node, indexed by node_id - 1.
+    /// None for unreachable nodes.
+    pub fn shortest_paths_from(&self, source: usize) -> Vec<Option<usize>> {
+        let mut distances: Vec<Option<usize>> = vec![None; self.node_count()];
+        let mut heap = BinaryHeap::new();
+        distances[source - 1] = Some(0);
+        heap.push(Reverse((0_usize, source)));
+
+        while let Some(Reverse((distance, node_id))) = heap.pop() {
+            if distances[node_id - 1].is_some_and(|best| best < distance) {
+                // stale entry, the node was settled with a shorter distance
+                continue;
+            }
+            for (adjacent_node_id, weight) in self.weighted_edges(node_id) {
+                let candidate = distance + weight;
+                if distances[adjacent_node_id - 1].is_none_or(|best| candidate < best) {
+                    distances[adjacent_node_id - 1] = Some(candidate);
+                    heap.push(Reverse((candidate, adjacent_node_id)));
+                }
+            }
+        }
+        distances
+    }
+}
+
+impl Graph for CsrGraph {
+    fn node_count(&self) -> usize {
+        CsrGraph::node_count(self)
+    }
+
+    fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+        self.adjacent_node_ids(node_id).iter().copied()
+    }
+}
+
+impl WeightedGraph for CsrGraph {
+    fn weighted_neighbours(&self, node_id: usize) -> impl Iterator<Item=(usize, usize)> + '_ {
+        self.weighted_edges(node_id)
+    }
+}
+
+impl From<&DirectedGraph> for CsrGraph {
+    fn from(graph: &DirectedGraph) -> Self {
+        let mut offsets = Vec::with_capacity(graph.nodes.len() + 1);
+        let mut targets = Vec::new();
+        offsets.push(0);
+        for node in graph.nodes.iter() {
+            targets.extend_from_slice(&node.adjacent_node_ids);
+            offsets.push(targets.len());
+        }
+        CsrGraph { offsets, targets, weights: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::{DirectedGraph, csr::CsrGraph};
+
+    fn read(path: &str) -> DirectedGraph {
+        DirectedGraph::from(utils::read_matrix(File::open(path).unwrap()))
+    }
+
+    #[test]
+    fn packs_adjacency_in_input_order() {
+        let graph = CsrGraph::from_edges(4, &[(3, 1), (1, 2), (3, 4), (1, 3)]);
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph.adjacent_node_ids(1), &[2, 3]);
+        assert_eq!(graph.adjacent_node_ids(2), &[] as &[usize]);
+        assert_eq!(graph.adjacent_node_ids(3), &[1, 4]);
+        assert_eq!(graph.reversed().adjacent_node_ids(1), &[3]);
+        assert_eq!(graph.reversed().reversed(), graph);
+    }
+
+    #[test]
+    fn same_components_as_directed_graph() {
+        for path in ["./test_data/1.txt", "./test_data/2.txt", "./test_data/3.txt",
+            "./test_data/4.txt", "./test_data/5.txt"] {
+            let graph = read(path);
+            let csr = CsrGraph::from(&graph);
+
+            assert_eq!(csr.strongly_connected_components(), graph.strongly_connected_components());
+        }
+    }
+
+    #[test]
+    fn topological_order_and_cycles() {
+        let dag = CsrGraph::from_edges(5, &[(5, 1), (1, 3), (2, 3), (3, 4), (5, 4)]);
+        assert_eq!(dag.topological_order_lexicographic().unwrap(), vec![2, 5, 1, 3, 4]);
+        assert_eq!(dag.topological_order().unwrap().len(), 5);
+
+        let cyclic = CsrGraph::from(&read("./test_data/1.txt"));
+        assert!(cyclic.topological_order().is_err());
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let graph = CsrGraph::from_weighted_edges(5, &[(1, 2, 7), (1, 3, 2), (3, 2, 3), (2, 4, 1), (3, 4, 9)]);
+
+        assert_eq!(graph.shortest_paths_from(1), vec![Some(0), Some(5), Some(2), Some(6), None]);
+        assert_eq!(graph.reversed().shortest_paths_from(4), vec![Some(6), Some(1), Some(4), Some(0), None]);
+    }
+}
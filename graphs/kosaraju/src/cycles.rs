@@ -0,0 +1,291 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{DirectedGraph, topo::Cycle};
+
+/// Lazy iterator over the elementary cycles (Johnson's algorithm), at most `limit` of them:
+/// a dense graph has exponentially many. Every cycle starts at its smallest node id,
+/// cycles come by increasing start. Cycles are node sequences, parallel edges don't repeat them.
+pub struct ElementaryCycles<'a> {
+    graph: &'a DirectedGraph,
+    // cycles left before the limit
+    remaining: usize,
+    // strongly connected node sets still to search, ascending, smallest first node on top
+    pending: BinaryHeap<Reverse<Vec<usize>>>,
+    // node set being searched, its smallest node starts every cycle
+    members: Vec<usize>,
+    start: usize,
+    in_component: Vec<bool>,
+    // node ids of the split component renumbered from 1, 0 outside of it
+    local_ids: Vec<usize>,
+    blocked: Vec<bool>,
+    // nodes to unblock together with a node, indexed by node_id - 1
+    blocked_by: Vec<Vec<usize>>,
+    stack: Vec<Frame>,
+    path: Vec<usize>,
+}
+
+struct Frame {
+    node_id: usize,
+    // distinct adjacent nodes inside the component, ascending
+    successors: Vec<usize>,
+    // index of the next successor to check
+    next: usize,
+    found_cycle: bool,
+}
+
+impl DirectedGraph {
+    /// Up to `limit` elementary cycles
+    pub fn elementary_cycles(&self, limit: usize) -> ElementaryCycles<'_> {
+        let node_count = self.nodes.len();
+        let mut cycles = ElementaryCycles {
+            graph: self,
+            remaining: limit,
+            pending: BinaryHeap::new(),
+            members: Vec::new(),
+            start: 0,
+            in_component: vec![false; node_count],
+            local_ids: vec![0; node_count],
+            blocked: vec![false; node_count],
+            blocked_by: vec![Vec::new(); node_count],
+            stack: Vec::new(),
+            path: Vec::new(),
+        };
+        for members in self.strongly_connected_components().members() {
+            cycles.push_if_cyclic(members);
+        }
+        cycles
+    }
+}
+
+impl ElementaryCycles<'_> {
+    // starts the search in the pending component with the smallest node
+    fn enter_next_component(&mut self) -> bool {
+        let Some(Reverse(members)) = self.pending.pop() else {
+            return false;
+        };
+        for member in members.iter() {
+            self.in_component[member - 1] = true;
+            self.blocked[member - 1] = false;
+            self.blocked_by[member - 1].clear();
+        }
+        self.start = members[0];
+        self.members = members;
+        self.blocked[self.start - 1] = true;
+        self.push_frame(self.start);
+        true
+    }
+
+    // every cycle through the start is found, the components of the rest of
+    // the searched component are all that need recomputing
+    fn split_searched_component(&mut self) {
+        let members = std::mem::take(&mut self.members);
+        if members.is_empty() {
+            return;
+        }
+        members.iter().for_each(|member| self.in_component[member - 1] = false);
+        let rest = &members[1..];
+        for (index, member) in rest.iter().enumerate() {
+            self.local_ids[member - 1] = index + 1;
+        }
+        let mut component = DirectedGraph::with_node_count(rest.len());
+        for member in rest.iter() {
+            for adjacent_node_id in self.graph.nodes[member - 1].adjacent_node_ids.iter() {
+                let local_id = self.local_ids[adjacent_node_id - 1];
+                if local_id != 0 {
+                    component.add_edge(self.local_ids[member - 1], local_id);
+                }
+            }
+        }
+        rest.iter().for_each(|member| self.local_ids[member - 1] = 0);
+        for local_members in component.strongly_connected_components().members() {
+            self.push_if_cyclic(local_members.iter().map(|local_id| rest[local_id - 1]).collect());
+        }
+    }
+
+    fn push_if_cyclic(&mut self, members: Vec<usize>) {
+        let node_id = members[0];
+        if members.len() > 1 || self.graph.nodes[node_id - 1].adjacent_node_ids.contains(&node_id) {
+            self.pending.push(Reverse(members));
+        }
+    }
+
+    fn push_frame(&mut self, node_id: usize) {
+        let mut successors = self.graph.nodes[node_id - 1].adjacent_node_ids.iter()
+            .copied()
+            .filter(|adjacent_node_id| self.in_component[adjacent_node_id - 1])
+            .collect::<Vec<usize>>();
+        successors.sort_unstable();
+        successors.dedup();
+        self.stack.push(Frame { node_id, successors, next: 0, found_cycle: false });
+        self.path.push(node_id);
+    }
+
+    fn unblock(&mut self, node_id: usize) {
+        let mut pending = vec![node_id];
+        while let Some(node_id) = pending.pop() {
+            if self.blocked[node_id - 1] {
+                self.blocked[node_id - 1] = false;
+                pending.append(&mut self.blocked_by[node_id - 1]);
+            }
+        }
+    }
+}
+
+impl Iterator for ElementaryCycles<'_> {
+    type Item = Cycle;
+
+    fn next(&mut self) -> Option<Cycle> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            if self.stack.is_empty() {
+                self.split_searched_component();
+                if !self.enter_next_component() {
+                    return None;
+                }
+            }
+            let frame = self.stack.last_mut().unwrap();
+            let node_id = frame.node_id;
+            match frame.successors.get(frame.next).copied() {
+                Some(adjacent_node_id) => {
+                    frame.next += 1;
+                    if adjacent_node_id == self.start {
+                        frame.found_cycle = true;
+                        self.remaining -= 1;
+                        return Some(Cycle { nodes: self.path.clone() });
+                    }
+                    if !self.blocked[adjacent_node_id - 1] {
+                        self.blocked[adjacent_node_id - 1] = true;
+                        self.push_frame(adjacent_node_id);
+                    }
+                }
+                None => {
+                    let frame = self.stack.pop().unwrap();
+                    self.path.pop();
+                    if frame.found_cycle {
+                        self.unblock(node_id);
+                    } else {
+                        // stays blocked until one of its successors gets unblocked
+                        for adjacent_node_id in frame.successors {
+                            let blocked_by = &mut self.blocked_by[adjacent_node_id - 1];
+                            if !blocked_by.contains(&node_id) {
+                                blocked_by.push(node_id);
+                            }
+                        }
+                    }
+                    if let Some(parent) = self.stack.last_mut() {
+                        parent.found_cycle |= frame.found_cycle;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::DirectedGraph;
+
+    fn complete_graph(node_count: usize) -> DirectedGraph {
+        let mut graph = DirectedGraph::default();
+        for from in 1..=node_count {
+            for to in (1..=node_count).filter(|to| *to != from) {
+                graph.add_edge(from, to);
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn counts_cycles_of_complete_graphs() {
+        assert_eq!(complete_graph(3).elementary_cycles(usize::MAX).count(), 5);
+        assert_eq!(complete_graph(4).elementary_cycles(usize::MAX).count(), 20);
+        assert_eq!(complete_graph(6).elementary_cycles(usize::MAX).count(), 409);
+    }
+
+    #[test]
+    fn lists_every_cycle_once() {
+        let mut graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/4.txt").unwrap()));
+        graph.add_edge(5, 5);
+        let mut cycles = graph.elementary_cycles(usize::MAX).map(|cycle| cycle.nodes).collect::<Vec<_>>();
+        cycles.sort();
+
+        assert_eq!(cycles, vec![vec![1, 2, 3], vec![3, 4], vec![4, 6], vec![5], vec![6, 7, 8]]);
+        assert!(DirectedGraph::from(utils::read_matrix(File::open("./test_data/3.txt").unwrap()))
+            .elementary_cycles(usize::MAX)
+            .all(|cycle| cycle.nodes.len() == 3));
+    }
+
+    #[test]
+    fn parallel_edges_dont_repeat_cycles() {
+        let mut graph = DirectedGraph::from(vec![vec![1, 2], vec![2, 1], vec![2, 3], vec![3, 1]]);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 3);
+        graph.add_edge(3, 3);
+        let mut cycles = graph.elementary_cycles(usize::MAX).map(|cycle| cycle.nodes).collect::<Vec<_>>();
+        cycles.sort();
+
+        assert_eq!(cycles, vec![vec![1, 2], vec![1, 2, 3], vec![3]]);
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        // about 10^8 cycles, only the first ones get searched
+        let graph = complete_graph(12);
+
+        let first = graph.elementary_cycles(3).map(|cycle| cycle.nodes).collect::<Vec<_>>();
+        assert_eq!(first, vec![vec![1, 2], vec![1, 2, 3], vec![1, 2, 3, 4]]);
+        assert_eq!(graph.elementary_cycles(1000).count(), 1000);
+        assert_eq!(graph.elementary_cycles(0).count(), 0);
+        assert_eq!(complete_graph(4).elementary_cycles(100).count(), 20);
+    }
+
+    // simple paths from every start through larger node ids back to the start
+    fn brute_force(graph: &DirectedGraph) -> Vec<Vec<usize>> {
+        fn extend(graph: &DirectedGraph, path: &mut Vec<usize>, cycles: &mut Vec<Vec<usize>>) {
+            let mut successors = graph.nodes[path[path.len() - 1] - 1].adjacent_node_ids.clone();
+            successors.sort_unstable();
+            successors.dedup();
+            for successor in successors {
+                if successor == path[0] {
+                    cycles.push(path.clone());
+                } else if successor > path[0] && !path.contains(&successor) {
+                    path.push(successor);
+                    extend(graph, path, cycles);
+                    path.pop();
+                }
+            }
+        }
+        let mut cycles = Vec::new();
+        for start in 1..=graph.node_count() {
+            extend(graph, &mut vec![start], &mut cycles);
+        }
+        cycles
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = XorShift64::new(37);
+        for _ in 0..200 {
+            let node_count = 1 + rng.below(7);
+            let mut graph = DirectedGraph::with_node_count(node_count);
+            for _ in 0..rng.below(3 * node_count) {
+                graph.add_edge(1 + rng.below(node_count), 1 + rng.below(node_count));
+            }
+            let cycles = graph.elementary_cycles(usize::MAX).map(|cycle| cycle.nodes).collect::<Vec<_>>();
+            let mut expected = brute_force(&graph);
+
+            assert!(cycles.windows(2).all(|pair| pair[0][0] <= pair[1][0]), "cycles by increasing start");
+            let mut cycles = cycles;
+            cycles.sort();
+            expected.sort();
+            assert_eq!(cycles, expected);
+        }
+    }
+}
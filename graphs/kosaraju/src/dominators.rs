@@ -0,0 +1,251 @@
+use crate::DirectedGraph;
+
+/// Dominator tree of the nodes reachable from a root: `a` dominates `b`
+/// when every path from the root to `b` goes through `a`
+#[derive(Debug, Clone)]
+pub struct Dominators {
+    root: usize,
+    // immediate dominator of every node, indexed by node_id - 1;
+    // None for the root and for unreachable nodes
+    idom: Vec<Option<usize>>,
+    // preorder and postorder numbers in the dominator tree, 0 for unreachable nodes
+    tree_pre: Vec<usize>,
+    tree_post: Vec<usize>,
+    // sorted dominance frontier of every node, indexed by node_id - 1
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl DirectedGraph {
+    /// Lengauer-Tarjan with path compression, all searches use explicit stacks
+    pub fn dominators(&self, root: usize) -> Dominators {
+        let node_count = self.nodes.len();
+        let predecessors = self.reversed();
+
+        // depth-first numbering from 1, number 0 marks unreachable nodes
+        let mut number = vec![0_usize; node_count];
+        // node id and spanning tree parent number of every depth-first number, index 0 unused
+        let mut vertex = vec![0_usize];
+        let mut parent = vec![0_usize];
+        let mut stack = vec![(root, 0_usize)];
+        while let Some((node_id, parent_number)) = stack.pop() {
+            if number[node_id - 1] != 0 {
+                continue;
+            }
+            vertex.push(node_id);
+            parent.push(parent_number);
+            number[node_id - 1] = vertex.len() - 1;
+            for adjacent_node_id in self.nodes[node_id - 1].adjacent_node_ids.iter().rev() {
+                if number[adjacent_node_id - 1] == 0 {
+                    stack.push((*adjacent_node_id, number[node_id - 1]));
+                }
+            }
+        }
+        let reachable = vertex.len() - 1;
+
+        // everything below works on depth-first numbers
+        let mut semi: Vec<usize> = (0..=reachable).collect();
+        let mut label: Vec<usize> = (0..=reachable).collect();
+        let mut ancestor = vec![0_usize; reachable + 1];
+        let mut dom = vec![0_usize; reachable + 1];
+        let mut bucket: Vec<Vec<usize>> = vec![Vec::new(); reachable + 1];
+        for w in (2..=reachable).rev() {
+            for predecessor in &predecessors.nodes[vertex[w] - 1].adjacent_node_ids {
+                let v = number[predecessor - 1];
+                if v == 0 {
+                    continue;
+                }
+                let u = eval(v, &mut ancestor, &mut label, &semi);
+                semi[w] = semi[w].min(semi[u]);
+            }
+            bucket[semi[w]].push(w);
+            ancestor[w] = parent[w];
+            for v in std::mem::take(&mut bucket[parent[w]]) {
+                let u = eval(v, &mut ancestor, &mut label, &semi);
+                dom[v] = if semi[u] < semi[v] { u } else { parent[w] };
+            }
+        }
+        for w in 2..=reachable {
+            if dom[w] != semi[w] {
+                dom[w] = dom[dom[w]];
+            }
+        }
+
+        let mut idom = vec![None; node_count];
+        for w in 2..=reachable {
+            idom[vertex[w] - 1] = Some(vertex[dom[w]]);
+        }
+        let (tree_pre, tree_post) = number_tree(root, &idom);
+        let frontiers = frontiers(&predecessors, &idom, &number);
+        Dominators { root, idom, tree_pre, tree_post, frontiers }
+    }
+}
+
+impl Dominators {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// None for the root and for nodes unreachable from it
+    pub fn immediate_dominator(&self, node_id: usize) -> Option<usize> {
+        self.idom[node_id - 1]
+    }
+
+    /// Immediate dominator of every node, indexed by node_id - 1
+    pub fn immediate_dominators(&self) -> &[Option<usize>] {
+        &self.idom
+    }
+
+    /// Every reachable node dominates itself, unreachable nodes take no part in dominance
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        self.tree_pre[a - 1] != 0
+            && self.tree_pre[b - 1] != 0
+            && self.tree_pre[a - 1] <= self.tree_pre[b - 1]
+            && self.tree_post[b - 1] <= self.tree_post[a - 1]
+    }
+
+    /// Nodes where the dominance of `node_id` ends: they have a predecessor
+    /// dominated by `node_id` but are not strictly dominated by it
+    pub fn dominance_frontier(&self, node_id: usize) -> &[usize] {
+        &self.frontiers[node_id - 1]
+    }
+}
+
+fn eval(v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v] == 0 {
+        return v;
+    }
+    // compress the path to the forest root, top-most node first
+    let mut path = Vec::new();
+    let mut current = v;
+    while ancestor[ancestor[current]] != 0 {
+        path.push(current);
+        current = ancestor[current];
+    }
+    while let Some(node) = path.pop() {
+        let up = ancestor[node];
+        if semi[label[up]] < semi[label[node]] {
+            label[node] = label[up];
+        }
+        ancestor[node] = ancestor[up];
+    }
+    label[v]
+}
+
+// preorder and postorder numbers of the dominator tree, starting from 1
+fn number_tree(root: usize, idom: &[Option<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let mut children = vec![Vec::new(); idom.len()];
+    for (index, dominator) in idom.iter().enumerate() {
+        if let Some(dominator) = dominator {
+            children[dominator - 1].push(index + 1);
+        }
+    }
+    let mut pre = vec![0; idom.len()];
+    let mut post = vec![0; idom.len()];
+    let (mut pre_counter, mut post_counter) = (1, 1);
+    pre[root - 1] = pre_counter;
+    let mut stack = vec![(root, 0_usize)];
+    while let Some((node_id, next)) = stack.last_mut() {
+        match children[*node_id - 1].get(*next) {
+            Some(&child) => {
+                *next += 1;
+                pre_counter += 1;
+                pre[child - 1] = pre_counter;
+                stack.push((child, 0));
+            }
+            None => {
+                post[*node_id - 1] = post_counter;
+                post_counter += 1;
+                stack.pop();
+            }
+        }
+    }
+    (pre, post)
+}
+
+// walks up the dominator tree from every predecessor of a node
+// until reaching the node's immediate dominator
+fn frontiers(predecessors: &DirectedGraph, idom: &[Option<usize>], number: &[usize]) -> Vec<Vec<usize>> {
+    let mut frontiers: Vec<Vec<usize>> = vec![Vec::new(); idom.len()];
+    for node_id in 1..=idom.len() {
+        if number[node_id - 1] == 0 {
+            continue;
+        }
+        for predecessor in &predecessors.nodes[node_id - 1].adjacent_node_ids {
+            if number[predecessor - 1] == 0 {
+                continue;
+            }
+            let mut runner = Some(*predecessor);
+            while runner.is_some() && runner != idom[node_id - 1] {
+                let current = runner.unwrap();
+                if frontiers[current - 1].last() != Some(&node_id) {
+                    frontiers[current - 1].push(node_id);
+                }
+                runner = idom[current - 1];
+            }
+        }
+    }
+    frontiers
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::DirectedGraph;
+
+    // a dominates b if b can't be reached from the root once a is removed
+    fn brute_force_dominates(graph: &DirectedGraph, root: usize, a: usize, b: usize) -> bool {
+        let reachable_without = |removed: usize| {
+            let mut explored = vec![false; graph.node_count()];
+            let mut stack = vec![root];
+            while let Some(node_id) = stack.pop() {
+                if node_id == removed || explored[node_id - 1] {
+                    continue;
+                }
+                explored[node_id - 1] = true;
+                stack.extend(graph.nodes[node_id - 1].adjacent_node_ids.iter());
+            }
+            explored
+        };
+        let reachable = reachable_without(0);
+        reachable[a - 1] && reachable[b - 1] && (a == b || !reachable_without(a)[b - 1])
+    }
+
+    #[test]
+    fn classic_control_flow_graph() {
+        // Lengauer and Tarjan's example with R..L renamed to 1..13
+        let mut graph = DirectedGraph::default();
+        for (from, to) in [(1, 2), (1, 3), (1, 4), (2, 1), (2, 4), (2, 5), (3, 6), (3, 7), (4, 5),
+            (5, 13), (6, 9), (7, 9), (7, 10), (8, 11), (9, 12), (10, 9), (11, 9), (11, 1), (12, 8), (12, 10), (13, 8)] {
+            graph.add_edge(from, to);
+        }
+        let dominators = graph.dominators(1);
+
+        assert_eq!(dominators.immediate_dominators(), &[
+            None, Some(1), Some(1), Some(1), Some(1), Some(3), Some(3),
+            Some(1), Some(1), Some(1), Some(8), Some(9), Some(5)
+        ]);
+        assert!(dominators.dominates(1, 12));
+        assert!(dominators.dominates(5, 13));
+        assert!(!dominators.dominates(3, 9));
+        assert_eq!(dominators.dominance_frontier(3), &[9, 10]);
+        assert_eq!(dominators.dominance_frontier(5), &[8]);
+        assert_eq!(dominators.dominance_frontier(11), &[1, 9]);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        for path in ["./test_data/1.txt", "./test_data/2.txt", "./test_data/4.txt", "./test_data/5.txt"] {
+            let graph = DirectedGraph::from(utils::read_matrix(File::open(path).unwrap()));
+            for root in [1, 2] {
+                let dominators = graph.dominators(root);
+                for a in 1..=graph.node_count() {
+                    for b in 1..=graph.node_count() {
+                        assert_eq!(dominators.dominates(a, b), brute_force_dominates(&graph, root, a, b),
+                                   "{} dominates {} from {} in {}", a, b, root, path);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use graph_core::union_find::UnionFind;
+
+use crate::{Components, DirectedGraph};
+
+/// Strongly connected components kept up to date while edges are added.
+///
+/// The components are kept in a topological order (Pearce and Kelly): an edge that agrees
+/// with the order is only recorded, otherwise the components reachable from `to` and those
+/// reaching `from` are searched, both limited to the positions between the two components.
+/// If the searches meet, the edge closes a cycle and the components found by both get merged;
+/// otherwise the two sets swap positions.
+#[derive(Debug, Default, Clone)]
+pub struct IncrementalScc {
+    graph: DirectedGraph,
+    components: UnionFind,
+    // far ends of the edges leaving and entering every component, read at its representative;
+    // edges that a merge turned internal are dropped by the next search through them
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+    // position of every component in the topological order, read at its representative
+    positions: Vec<usize>,
+    // number of the last search that reached the component, forward and backward
+    forward_marks: Vec<usize>,
+    backward_marks: Vec<usize>,
+    searches: usize,
+}
+
+impl IncrementalScc {
+    pub fn with_node_count(count: usize) -> Self {
+        let mut scc = IncrementalScc::default();
+        scc.ensure_allocation_for(count);
+        scc
+    }
+
+    /// Starts from the components of an existing graph, computed once with Kosaraju
+    pub fn new(graph: DirectedGraph) -> Self {
+        let node_count = graph.node_count();
+        let mut scc = IncrementalScc::with_node_count(node_count);
+        let scc_members = graph.strongly_connected_components().members();
+        // component ids are in reverse topological order
+        for (index, members) in scc_members.iter().enumerate() {
+            members.iter().for_each(|member| { scc.components.union(members[0], *member); });
+            let representative = scc.components.find(members[0]);
+            scc.positions[representative - 1] = scc_members.len() - 1 - index;
+        }
+        for node in graph.nodes.iter() {
+            let source = scc.components.find(node.meta.node_id);
+            for adjacent_node_id in node.adjacent_node_ids.iter().copied() {
+                let target = scc.components.find(adjacent_node_id);
+                if source != target {
+                    scc.outgoing[source - 1].push(adjacent_node_id);
+                    scc.incoming[target - 1].push(node.meta.node_id);
+                }
+            }
+        }
+        scc.graph = graph;
+        scc
+    }
+
+    pub fn graph(&self) -> &DirectedGraph {
+        &self.graph
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Adds the edge and returns true if it merged components
+    pub fn add_edge(&mut self, from: usize, to: usize) -> bool {
+        self.graph.add_edge(from, to);
+        self.ensure_allocation_for(self.graph.node_count());
+        let (source, target) = (self.components.find(from), self.components.find(to));
+        if source == target {
+            return false;
+        }
+        self.outgoing[source - 1].push(to);
+        self.incoming[target - 1].push(from);
+        let (lower, upper) = (self.positions[target - 1], self.positions[source - 1]);
+        if upper < lower {
+            return false;
+        }
+
+        // only components placed between the two can lie on a path from `to` to `from`
+        self.searches += 1;
+        let mark = self.searches;
+        let forward = self.search(target, upper, true);
+        let mut backward = self.search(source, lower, false);
+        let closes_cycle = self.forward_marks[source - 1] == mark;
+
+        // components reaching `from` take the lowest of the positions involved, in their old order,
+        // then come the ones reachable from `to`; the ones found by both merge
+        let (merged, mut forward): (Vec<usize>, Vec<usize>) = forward.into_iter()
+            .partition(|component_id| self.backward_marks[component_id - 1] == mark);
+        backward.sort_unstable_by_key(|component_id| self.positions[component_id - 1]);
+        forward.sort_unstable_by_key(|component_id| self.positions[component_id - 1]);
+        let mut slots = backward.iter().chain(forward.iter())
+            .map(|component_id| self.positions[component_id - 1])
+            .collect::<Vec<usize>>();
+        slots.sort_unstable();
+        for (component_id, slot) in backward.iter().chain(forward.iter()).zip(slots) {
+            self.positions[component_id - 1] = slot;
+        }
+        if !closes_cycle {
+            return false;
+        }
+
+        // `from` has the highest position of the backward side, the merged component keeps it
+        let slot = self.positions[source - 1];
+        merged.iter().for_each(|component_id| { self.components.union(source, *component_id); });
+        let representative = self.components.find(source);
+        self.positions[representative - 1] = slot;
+        for component_id in merged.into_iter().filter(|component_id| *component_id != representative) {
+            for edges in [&mut self.outgoing, &mut self.incoming] {
+                let mut moved = std::mem::take(&mut edges[component_id - 1]);
+                if moved.len() > edges[representative - 1].len() {
+                    std::mem::swap(&mut moved, &mut edges[representative - 1]);
+                }
+                edges[representative - 1].append(&mut moved);
+            }
+        }
+        true
+    }
+
+    /// Representative node of the component holding `node_id`;
+    /// it changes when the component gets merged with another one
+    pub fn component_of(&mut self, node_id: usize) -> usize {
+        self.components.find(node_id)
+    }
+
+    pub fn same_component(&mut self, a: usize, b: usize) -> bool {
+        self.components.same_set(a, b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components.set_count()
+    }
+
+    /// Current components, numbered by their smallest node id
+    pub fn components(&mut self) -> Components {
+        self.components.components()
+    }
+
+    // components reached from `start` along edges (forward) or against them,
+    // staying within positions at most (forward) or at least `bound`
+    fn search(&mut self, start: usize, bound: usize, forward: bool) -> Vec<usize> {
+        let IncrementalScc { components, outgoing, incoming, positions, forward_marks, backward_marks, searches, .. } = self;
+        let (edges, marks) = if forward { (outgoing, forward_marks) } else { (incoming, backward_marks) };
+        marks[start - 1] = *searches;
+        let mut reached = vec![start];
+        let mut stack = vec![start];
+        while let Some(component_id) = stack.pop() {
+            edges[component_id - 1].retain(|node_id| components.find(*node_id) != component_id);
+            for node_id in edges[component_id - 1].iter() {
+                let adjacent_id = components.find(*node_id);
+                let position = positions[adjacent_id - 1];
+                let in_range = if forward { position <= bound } else { position >= bound };
+                if in_range && marks[adjacent_id - 1] != *searches {
+                    marks[adjacent_id - 1] = *searches;
+                    reached.push(adjacent_id);
+                    stack.push(adjacent_id);
+                }
+            }
+        }
+        reached
+    }
+
+    fn ensure_allocation_for(&mut self, count: usize) {
+        self.graph.ensure_allocation_for(count);
+        self.outgoing.resize(count.max(self.outgoing.len()), Vec::new());
+        self.incoming.resize(count.max(self.incoming.len()), Vec::new());
+        // new nodes go after every existing one
+        let node_count = self.positions.len();
+        self.positions.extend(node_count..count);
+        self.forward_marks.resize(count.max(self.forward_marks.len()), 0);
+        self.backward_marks.resize(count.max(self.backward_marks.len()), 0);
+        self.components.extend_to(count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::{Components, DirectedGraph, incremental::IncrementalScc};
+
+    fn partition(components: &Components) -> Vec<Vec<usize>> {
+        let mut members = components.members();
+        members.sort();
+        members
+    }
+
+    // every edge between components goes forward in the maintained order
+    fn assert_topological(scc: &mut IncrementalScc) {
+        for from in 1..=scc.node_count() {
+            let adjacent_node_ids = scc.graph.nodes[from - 1].adjacent_node_ids.clone();
+            for to in adjacent_node_ids {
+                let (source, target) = (scc.component_of(from), scc.component_of(to));
+                if source != target {
+                    assert!(scc.positions[source - 1] < scc.positions[target - 1], "{} -> {}", from, to);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn merges_when_edge_closes_cycle() {
+        let mut scc = IncrementalScc::with_node_count(5);
+
+        assert!(!scc.add_edge(1, 2));
+        assert!(!scc.add_edge(2, 3));
+        assert!(!scc.add_edge(4, 5));
+        assert_eq!(scc.component_count(), 5);
+        assert!(scc.add_edge(3, 1));
+        assert!(scc.same_component(1, 3));
+        assert!(!scc.same_component(3, 4));
+        assert!(!scc.add_edge(2, 1));
+        assert!(scc.add_edge(5, 4));
+        assert!(!scc.add_edge(3, 4));
+        assert!(scc.add_edge(4, 2));
+        assert_eq!(scc.component_count(), 1);
+    }
+
+    #[test]
+    fn starts_from_existing_graph() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/4.txt").unwrap()));
+        let mut scc = IncrementalScc::new(graph.clone());
+
+        assert_eq!(partition(&scc.components()), partition(&graph.strongly_connected_components()));
+        assert_topological(&mut scc);
+    }
+
+    #[test]
+    fn chain_added_from_its_end() {
+        // every edge agrees with the order, so none of them searches
+        let node_count = 50_000;
+        let mut scc = IncrementalScc::with_node_count(node_count);
+        for node_id in (1..node_count).rev() {
+            assert!(!scc.add_edge(node_id, node_id + 1));
+        }
+        assert_eq!(scc.component_count(), node_count);
+
+        // closes one big cycle
+        assert!(scc.add_edge(node_count, 1));
+        assert_eq!(scc.component_count(), 1);
+    }
+
+    #[test]
+    fn reorders_without_merging() {
+        let mut scc = IncrementalScc::with_node_count(6);
+        assert!(!scc.add_edge(5, 6));
+        // against the initial order: 5 and 6 move before 1 and 2
+        assert!(!scc.add_edge(6, 1));
+        assert!(!scc.add_edge(1, 2));
+        assert!(!scc.add_edge(4, 3));
+        assert_topological(&mut scc);
+        assert_eq!(scc.component_count(), 6);
+
+        assert!(scc.add_edge(2, 5));
+        assert!(scc.same_component(1, 5));
+        assert!(!scc.same_component(3, 4));
+        assert_eq!(scc.component_count(), 3);
+        assert_topological(&mut scc);
+    }
+
+    #[test]
+    fn agrees_with_kosaraju_after_random_insertions() {
+        let mut rng = XorShift64::new(42);
+        for _ in 0..50 {
+            let node_count = 1 + rng.below(60);
+            let mut scc = IncrementalScc::default();
+            let mut graph = DirectedGraph::default();
+            for _ in 0..rng.below(3 * node_count) {
+                let (from, to) = (1 + rng.below(node_count), 1 + rng.below(node_count));
+                scc.add_edge(from, to);
+                graph.add_edge(from, to);
+
+                let expected = graph.strongly_connected_components();
+                assert_eq!(scc.component_count(), expected.count());
+                assert_eq!(partition(&scc.components()), partition(&expected));
+                assert_topological(&mut scc);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{DirectedGraph, topo::Cycle};
+
+/// Directed graph over arbitrary labels (strings, sparse ids, ...).
+/// Every new label gets the next dense node id of the underlying [`DirectedGraph`],
+/// and results are translated back to labels.
+#[derive(Debug, Clone)]
+pub struct LabelledGraph<L> {
+    graph: DirectedGraph,
+    // label of every node, indexed by node_id - 1
+    labels: Vec<L>,
+    node_ids: HashMap<L, usize>,
+}
+
+impl<L> Default for LabelledGraph<L> {
+    fn default() -> Self {
+        LabelledGraph { graph: DirectedGraph::default(), labels: Vec::new(), node_ids: HashMap::new() }
+    }
+}
+
+impl<L: Hash + Eq + Clone> LabelledGraph<L> {
+    /// Returns the node id of the label, adding an isolated node for a new one
+    pub fn add_node(&mut self, label: L) -> usize {
+        if let Some(node_id) = self.node_ids.get(&label) {
+            return *node_id;
+        }
+        let node_id = self.labels.len() + 1;
+        self.graph.ensure_allocation_for(node_id);
+        self.labels.push(label.clone());
+        self.node_ids.insert(label, node_id);
+        node_id
+    }
+
+    pub fn add_edge(&mut self, from: L, to: L) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.graph.add_edge(from, to)
+    }
+
+    pub fn node_id(&self, label: &L) -> Option<usize> {
+        self.node_ids.get(label).copied()
+    }
+
+    pub fn label(&self, node_id: usize) -> &L {
+        &self.labels[node_id - 1]
+    }
+
+    /// Underlying graph with dense node ids
+    pub fn graph(&self) -> &DirectedGraph {
+        &self.graph
+    }
+
+    /// Labels of every strongly connected component, indexed by component id - 1
+    /// (see [`DirectedGraph::strongly_connected_components`] for the numbering)
+    pub fn strongly_connected_components(&self) -> Vec<Vec<L>> {
+        self.graph.strongly_connected_components()
+            .members()
+            .into_iter()
+            .map(|members| self.labels_of(&members))
+            .collect()
+    }
+
+    /// See [`DirectedGraph::topological_order`]
+    pub fn topological_order(&self) -> Result<Vec<L>, Cycle<L>> {
+        self.translate(self.graph.topological_order())
+    }
+
+    /// See [`DirectedGraph::topological_order_lexicographic`], ties are broken by insertion order
+    pub fn topological_order_lexicographic(&self) -> Result<Vec<L>, Cycle<L>> {
+        self.translate(self.graph.topological_order_lexicographic())
+    }
+
+    fn translate(&self, order: Result<Vec<usize>, Cycle>) -> Result<Vec<L>, Cycle<L>> {
+        order
+            .map(|node_ids| self.labels_of(&node_ids))
+            .map_err(|cycle| Cycle { nodes: self.labels_of(&cycle.nodes) })
+    }
+
+    fn labels_of(&self, node_ids: &[usize]) -> Vec<L> {
+        node_ids.iter().map(|node_id| self.label(*node_id).clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::labelled::LabelledGraph;
+
+    #[test]
+    fn sparse_ids_stay_dense_internally() {
+        let mut graph = LabelledGraph::<u64>::default();
+        graph.add_edge(1, 4_000_000_000);
+        graph.add_edge(4_000_000_000, 0);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 17);
+
+        assert_eq!(graph.graph().node_count(), 4);
+        assert_eq!(graph.node_id(&0), Some(3));
+        assert_eq!(*graph.label(2), 4_000_000_000);
+
+        let mut components = graph.strongly_connected_components();
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort();
+        assert_eq!(components, vec![vec![0, 1, 4_000_000_000], vec![17]]);
+    }
+
+    #[test]
+    fn topological_order_of_labels() {
+        let mut graph = LabelledGraph::default();
+        graph.add_node("app");
+        graph.add_edge("core", "net");
+        graph.add_edge("core", "app");
+        graph.add_edge("net", "app");
+        graph.add_node("docs");
+
+        assert_eq!(graph.topological_order_lexicographic().unwrap(), vec!["core", "net", "app", "docs"]);
+
+        graph.add_edge("app", "core");
+        let cycle = graph.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes.len(), 3);
+        assert!(cycle.to_string().starts_with("graph contains a cycle: "));
+    }
+}
@@ -1,54 +1,110 @@
-use std::{
-    borrow::{Borrow, BorrowMut},
-    cell::RefCell,
-    collections::HashMap,
-    rc::Rc,
-    usize,
-};
+use std::rc::Rc;
+
+pub use graph_core::{Components, ComponentSize};
+use graph_core::Graph;
+
+pub mod augmentation;
+pub mod centrality;
+pub mod condensation;
+pub mod csr;
+pub mod cycles;
+pub mod dominators;
+pub mod incremental;
+pub mod labelled;
+pub mod loader;
+pub mod parallel;
+pub mod reachability;
+pub mod reduction;
+pub mod schedule;
+pub mod subgraph;
+pub mod topo;
+pub mod two_sat;
 
 #[derive(Debug, Default, Clone)]
 pub struct DirectedGraph {
-    nodes: Vec<RefCell<Node>>,
+    nodes: Vec<Node>,
 }
 
 
 impl DirectedGraph {
+    /// Graph with `count` isolated nodes, ids from 1 to `count`
+    pub fn with_node_count(count: usize) -> Self {
+        let mut graph = DirectedGraph::default();
+        graph.ensure_allocation_for(count);
+        graph
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Node ids are dense and start from 1, every id up to the largest one gets allocated;
+    /// see [`labelled::LabelledGraph`] for arbitrary or sparse ids
     pub fn add_edge(&mut self, from: usize, to: usize) {
+        assert!(from > 0 && to > 0, "Node ids start from 1");
         self.ensure_allocation_for(from);
         self.ensure_allocation_for(to);
         // assign node index
 
-        self.nodes[from - 1].borrow_mut().meta.node_id = from;
+        self.nodes[from - 1].meta.node_id = from;
         // connect
-        self.nodes[from - 1].borrow_mut().connect_to(to)
+        self.nodes[from - 1].connect_to(to)
     }
 
     fn ensure_allocation_for(&mut self, cnt: usize) {
         let last_existing_node_id = if self.nodes.is_empty() { 0 } else { self.nodes.len() };
         if last_existing_node_id < cnt {
             for newly_created_node_id in (last_existing_node_id + 1)..=cnt {
-                let node = Node::new();
-                node.borrow_mut().meta.node_id = newly_created_node_id;
+                let mut node = Node::default();
+                node.meta.node_id = newly_created_node_id;
                 self.nodes.push(node)
             }
         }
     }
 
     pub fn reversed(&self) -> DirectedGraph {
-        let mut reversed_graph = DirectedGraph::default();
+        // keep trailing nodes without incoming edges
+        let mut reversed_graph = DirectedGraph::with_node_count(self.nodes.len());
 
         for node in self.nodes.iter() {
-            for adj_node_id in node.borrow().adjacent_node_ids.iter() {
-                reversed_graph.add_edge(*adj_node_id, node.borrow().meta.node_id)
+            for adj_node_id in node.adjacent_node_ids.iter() {
+                reversed_graph.add_edge(*adj_node_id, node.meta.node_id)
             }
         }
         reversed_graph
     }
 
-    fn set_unvisited(&self) {
-        for node in self.nodes.iter() {
-            node.borrow_mut().mark_unexplored();
-        }
+    /// Strongly connected components computed with Kosaraju's algorithm.
+    /// Components are numbered in reverse topological order: component 1 is a sink of the
+    /// condensation, and every edge between components goes from a higher id to a lower one.
+    pub fn strongly_connected_components(&self) -> Components {
+        kosaraju(self)
+    }
+
+    /// Components of the graph with edge directions ignored, numbered by their smallest node id
+    pub fn weakly_connected_components(&self) -> Components {
+        graph_core::union_find::connected_components(self)
+    }
+
+    /// Number of edges on a shortest path from `source` to every node, indexed by node_id - 1;
+    /// None for unreachable nodes
+    pub fn distances_from(&self, source: usize) -> Vec<Option<usize>> {
+        graph_core::traversal::breadth_first_distances(self, source)
+    }
+
+    /// Up to `k` largest strongly connected components, largest first
+    pub fn largest_components(&self, k: usize) -> Vec<ComponentSize> {
+        self.strongly_connected_components().largest(k)
+    }
+}
+
+impl Graph for DirectedGraph {
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn neighbours(&self, node_id: usize) -> impl Iterator<Item=usize> + '_ {
+        self.nodes[node_id - 1].adjacent_node_ids.iter().copied()
     }
 }
 
@@ -71,14 +127,6 @@ impl Node {
     fn mark_as_explored(&mut self) {
         self.meta.mark_as_visited()
     }
-
-    fn mark_unexplored(&mut self) {
-        self.meta.is_explored = false
-    }
-
-    fn new() -> RefCell<Node> {
-        RefCell::new(Node::default())
-    }
 }
 
 impl From<Vec<Vec<usize>>> for DirectedGraph {
@@ -103,94 +151,84 @@ impl TraverseMeta {
     }
 }
 
-fn topo_sort(graph: Rc<DirectedGraph>) {
+// labels nodes by decreasing finishing time, which is a topological order only for acyclic
+// graphs; Kosaraju needs exactly this labelling, see DirectedGraph::topological_order otherwise
+fn topo_sort(graph: &mut DirectedGraph) {
     let mut cur_label = graph.nodes.len();
-    for (index, node) in graph.nodes.iter().enumerate() {
-        if !node.borrow().is_explored() {
-            dfs_topo(Rc::clone(&graph), index, &mut cur_label)
+    for index in 0..graph.nodes.len() {
+        if !graph.nodes[index].is_explored() {
+            dfs_topo(graph, index, &mut cur_label)
         }
     }
 }
 
-fn dfs_topo(graph: Rc<DirectedGraph>, node_id: usize, topo_label: &mut usize) {
-    let selected_node = graph.nodes.get(node_id).unwrap();
-    selected_node.borrow_mut().mark_as_explored();
-    for node_id in &selected_node.borrow().adjacent_node_ids {
-        if !graph.nodes[node_id - 1].borrow().is_explored() {
-            dfs_topo(Rc::clone(&graph), node_id - 1, topo_label)
+// explicit stack of (node index, position of the next adjacent node to look at),
+// so that long paths such as the ones in SCC.txt don't overflow the call stack
+fn dfs_topo(graph: &mut DirectedGraph, node_index: usize, topo_label: &mut usize) {
+    graph.nodes[node_index].mark_as_explored();
+    let mut stack = vec![(node_index, 0_usize)];
+    while let Some((index, next)) = stack.last_mut() {
+        if let Some(adjacent_node_id) = graph.nodes[*index].adjacent_node_ids.get(*next).copied() {
+            *next += 1;
+            let adjacent_node = &mut graph.nodes[adjacent_node_id - 1];
+            if !adjacent_node.is_explored() {
+                adjacent_node.mark_as_explored();
+                stack.push((adjacent_node_id - 1, 0));
+            }
+        } else {
+            graph.nodes[*index].meta.topo_order = *topo_label;
+            *topo_label -= 1;
+            stack.pop();
         }
     }
-    selected_node.borrow_mut().meta.topo_order = *topo_label;
-    *topo_label -= 1;
 }
 
-fn dfs_scc(graph: Rc<DirectedGraph>, node_id: usize, component_counter: usize) -> usize {
-    let visited_node = graph.nodes.get(node_id - 1).unwrap();
-    visited_node.borrow_mut().mark_as_explored();
-    let mut inner_counter = 0_usize;
-    for adjacent_node_id in &visited_node.borrow().adjacent_node_ids {
-        if !graph.nodes[adjacent_node_id - 1].borrow().is_explored() {
-            inner_counter += dfs_scc(
-                Rc::clone(&graph),
-                *adjacent_node_id,
-                1);
+// a node is explored on the source graph once its membership is set, so the source graph
+// itself is only read and `kosaraju` can take it by shared reference
+fn dfs_scc(graph: &DirectedGraph, node_id: usize, membership: &mut [usize], scc_index: usize) {
+    membership[node_id - 1] = scc_index;
+    let mut stack = vec![node_id];
+    while let Some(visited_node_id) = stack.pop() {
+        for adjacent_node_id in graph.nodes[visited_node_id - 1].adjacent_node_ids.iter() {
+            if membership[adjacent_node_id - 1] == 0 {
+                membership[adjacent_node_id - 1] = scc_index;
+                stack.push(*adjacent_node_id);
+            }
         }
     }
-    component_counter + inner_counter
 }
 
-fn kosaraju(source_graph: Rc<DirectedGraph>) -> HashMap<usize, usize> {
+fn kosaraju(source_graph: &DirectedGraph) -> Components {
     // reverse and topo-sort source graph
-    let rev = Rc::new(source_graph.reversed());
-    topo_sort(Rc::clone(&rev));
-    let mut reversed_copy = rev.as_ref().clone();
-
-    reversed_copy.nodes.sort_by(|lnode, rnode| {
-        lnode
-            .borrow()
-            .meta
-            .topo_order
-            .partial_cmp(&rnode.borrow().meta.topo_order)
-            .unwrap()
-    });
-    // mark all verticies as unvisited for second df-search
-    reversed_copy.set_unvisited();
-    let reversed_graph = Rc::new(reversed_copy);
+    let mut reversed_graph = source_graph.reversed();
+    topo_sort(&mut reversed_graph);
+
+    reversed_graph.nodes.sort_by_key(|node| node.meta.topo_order);
 
     let mut scc_index = 0_usize;
-    let mut scc_map = HashMap::<usize, usize>::new();
-
-    for ( node) in reversed_graph.nodes.iter() {
-        let is_explored_on_source_graph = source_graph
-            .nodes[node.borrow().meta.node_id - 1]
-            .borrow()
-            .is_explored();
-        if !is_explored_on_source_graph {
+    let mut membership = vec![0; source_graph.nodes.len()];
+
+    for node in reversed_graph.nodes.iter() {
+        let node_id = node.meta.node_id;
+        if membership[node_id - 1] == 0 {
             scc_index += 1;
-            let number_of_components = dfs_scc(
-                Rc::clone(&source_graph),
-                node.borrow().meta.node_id,
-                1);
-            scc_map.insert(scc_index, number_of_components);
+            dfs_scc(source_graph, node_id, &mut membership, scc_index);
         }
     }
-    scc_map
+    Components::from_membership(membership)
 }
 
 
-fn top_5_scc_sizes(graph: Rc<DirectedGraph>) -> Vec<usize> {
-    let map = kosaraju(graph);
-    let mut vals: Vec<usize> = map.into_values().collect();
-    vals.sort();
-    vals.reverse();
-    vals.resize(5, 0);
-    vals
+pub fn top_5_scc_sizes(graph: Rc<DirectedGraph>) -> Vec<usize> {
+    kosaraju(&graph).largest_sizes_padded(5, 0)
 }
 
 #[cfg(test)]
 mod tests {
     use std::{fs::File, rc::Rc};
 
+    use graph_core::{Graph, scc, traversal};
+
     use crate::{DirectedGraph, top_5_scc_sizes};
 
     #[test]
@@ -201,30 +239,63 @@ mod tests {
         graph.add_edge(4, 5);
 
         assert_eq!(graph.nodes.len(), 5);
-        assert_eq!(graph.nodes[0].borrow().meta.node_id, 1);
-        assert_eq!(graph.nodes[1].borrow().meta.node_id, 2);
-        assert_eq!(graph.nodes[2].borrow().meta.node_id, 3);
-        assert_eq!(graph.nodes[3].borrow().meta.node_id, 4);
-        assert_eq!(graph.nodes[4].borrow().meta.node_id, 5);
-
-        assert_eq!(graph.nodes[0].borrow().adjacent_node_ids, vec![2, 3]);
-        assert_eq!(graph.nodes[1].borrow().adjacent_node_ids, vec![]);
-        assert_eq!(graph.nodes[2].borrow().adjacent_node_ids, vec![]);
-        assert_eq!(graph.nodes[3].borrow().adjacent_node_ids, vec![5]);
-        assert_eq!(graph.nodes[4].borrow().adjacent_node_ids, vec![]);
+        assert_eq!(graph.nodes[0].meta.node_id, 1);
+        assert_eq!(graph.nodes[1].meta.node_id, 2);
+        assert_eq!(graph.nodes[2].meta.node_id, 3);
+        assert_eq!(graph.nodes[3].meta.node_id, 4);
+        assert_eq!(graph.nodes[4].meta.node_id, 5);
+
+        assert_eq!(graph.nodes[0].adjacent_node_ids, vec![2, 3]);
+        assert_eq!(graph.nodes[1].adjacent_node_ids, vec![]);
+        assert_eq!(graph.nodes[2].adjacent_node_ids, vec![]);
+        assert_eq!(graph.nodes[3].adjacent_node_ids, vec![5]);
+        assert_eq!(graph.nodes[4].adjacent_node_ids, vec![]);
 
         let reversed = graph.reversed();
         dbg!(&reversed);
         assert_eq!(reversed.nodes.len(), 5);
-        assert_eq!(reversed.nodes[0].borrow().adjacent_node_ids, vec![]);
-        assert_eq!(reversed.nodes[1].borrow().adjacent_node_ids, vec![1]);
-        assert_eq!(reversed.nodes[2].borrow().adjacent_node_ids, vec![1]);
-        assert_eq!(reversed.nodes[3].borrow().adjacent_node_ids, vec![]);
-        assert_eq!(reversed.nodes[4].borrow().adjacent_node_ids, vec![4]);
+        assert_eq!(reversed.nodes[0].adjacent_node_ids, vec![]);
+        assert_eq!(reversed.nodes[1].adjacent_node_ids, vec![1]);
+        assert_eq!(reversed.nodes[2].adjacent_node_ids, vec![1]);
+        assert_eq!(reversed.nodes[3].adjacent_node_ids, vec![]);
+        assert_eq!(reversed.nodes[4].adjacent_node_ids, vec![4]);
 
         // dbg!(graph);
     }
 
+    #[test]
+    #[should_panic(expected = "Node ids start from 1")]
+    fn zero_id_is_rejected_by_dense_graph() {
+        DirectedGraph::default().add_edge(0, 1);
+    }
+
+    #[test]
+    fn components_while_iterating_neighbours() {
+        let graph = DirectedGraph::from(vec![vec![1, 2], vec![2, 1], vec![2, 3]]);
+        let mut neighbours = graph.neighbours(2);
+        assert_eq!(graph.strongly_connected_components().count(), 2);
+        assert_eq!(neighbours.next(), Some(1));
+        assert_eq!(graph.strongly_connected_components().count(), 2);
+        assert_eq!(neighbours.collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn long_cycle_does_not_overflow_the_stack() {
+        let node_count = 1_000_000;
+        let mut graph = DirectedGraph::with_node_count(node_count);
+        for node_id in 1..node_count {
+            graph.add_edge(node_id, node_id + 1);
+        }
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.count(), node_count);
+        // reverse topological numbering: the end of the chain is the sink
+        assert_eq!(components.component_of(node_count), 1);
+        assert_eq!(components.component_of(1), node_count);
+
+        graph.add_edge(node_count, 1);
+        assert_eq!(graph.strongly_connected_components().count(), 1);
+    }
+
     #[test]
     fn test_one() {
         let m1 = File::open("./test_data/1.txt").unwrap();
@@ -270,4 +341,64 @@ mod tests {
 
         assert_eq!(top_5_scc_sizes(graph), [6, 3, 2, 1, 0]);
     }
+
+    #[test]
+    fn generic_algorithms_agree_with_kosaraju() {
+        for path in ["./test_data/1.txt", "./test_data/2.txt", "./test_data/3.txt",
+            "./test_data/4.txt", "./test_data/5.txt"] {
+            let graph = DirectedGraph::from(utils::read_matrix(File::open(path).unwrap()));
+            let mut kosaraju = graph.strongly_connected_components().members();
+            let mut tarjan = scc::strongly_connected_components(&graph).members();
+            kosaraju.sort();
+            tarjan.sort();
+
+            assert_eq!(kosaraju, tarjan);
+        }
+
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/1.txt").unwrap()));
+        assert_eq!(traversal::depth_first(&graph, 1), vec![1, 4, 7]);
+        assert_eq!(traversal::breadth_first(&graph, 8), vec![8, 5, 6, 2, 9, 7, 3, 1, 4]);
+    }
+
+    #[test]
+    fn largest_components_keep_ids() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/5.txt").unwrap()));
+        let components = graph.strongly_connected_components();
+        let largest = graph.largest_components(2);
+
+        assert_eq!(largest.len(), 2);
+        assert_eq!(largest[0].size, 6);
+        assert_eq!(largest[0].component_id, components.component_of(7));
+        assert_eq!(largest[1].size, 3);
+        assert_eq!(largest[1].component_id, components.component_of(2));
+        assert_eq!(graph.largest_components(10).len(), 4);
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        let mut graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/1.txt").unwrap()));
+        assert_eq!(graph.weakly_connected_components().count(), 1);
+
+        // 10 -> 11 <- 12 joins neither the rest nor strongly
+        graph.add_edge(10, 11);
+        graph.add_edge(12, 11);
+        let weak = graph.weakly_connected_components();
+        let strong = graph.strongly_connected_components();
+
+        assert_eq!(weak.count(), 2);
+        assert_eq!(weak.members()[1], vec![10, 11, 12]);
+        assert_eq!(strong.count(), 6);
+        // every strongly connected component lies inside one weak component
+        for members in strong.members() {
+            assert!(members.iter().all(|member| weak.component_of(*member) == weak.component_of(members[0])));
+        }
+    }
+
+    #[test]
+    fn breadth_first_distances() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/4.txt").unwrap()));
+
+        assert_eq!(graph.distances_from(1), vec![Some(0), Some(1), Some(2), Some(3), None, Some(4), Some(5), Some(6)]);
+        assert_eq!(graph.distances_from(5)[2], Some(2));
+    }
 }
@@ -0,0 +1,220 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{DirectedGraph, Node};
+
+// largest node id accepted without a header, node storage is dense up to the largest id
+const MAX_NODE_ID_WITHOUT_HEADER: usize = 1 << 24;
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    /// malformed line, lines are numbered from 1
+    Parse { line: usize, message: String },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "can't read edge list: {}", error),
+            LoadError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+enum Line {
+    Empty,
+    Header(usize),
+    Edge(usize, usize),
+}
+
+impl DirectedGraph {
+    /// Streams an edge list (a `from to` pair per line) straight into the graph
+    /// without keeping the text in memory. Blank lines and lines starting with `#` are skipped.
+    /// An optional header, a line with a single number before the first edge, is the node count:
+    /// it pre-sizes the graph and bounds the node ids, which are otherwise limited to 2^24;
+    /// see [`crate::labelled::LabelledGraph`] for sparse ids.
+    pub fn from_edge_list<R: BufRead>(mut reader: R) -> Result<DirectedGraph, LoadError> {
+        let mut graph = DirectedGraph::default();
+        let mut buffer = String::new();
+        let mut parser = LineParser::default();
+        while reader.read_line(&mut buffer)? > 0 {
+            match parser.parse(&buffer)? {
+                Line::Empty => {}
+                Line::Header(node_count) => {
+                    graph.nodes.reserve(node_count);
+                    graph.ensure_allocation_for(node_count);
+                }
+                Line::Edge(from, to) => graph.add_edge(from, to),
+            }
+            buffer.clear();
+        }
+        Ok(graph)
+    }
+
+    /// Reads the file twice: the first pass counts nodes and out-degrees so that
+    /// the second one fills exactly sized adjacency vectors. Same format as
+    /// [`DirectedGraph::from_edge_list`].
+    pub fn from_edge_list_file<P: AsRef<Path>>(path: P) -> Result<DirectedGraph, LoadError> {
+        let mut out_degrees: Vec<usize> = Vec::new();
+        for_each_line(&path, |line| {
+            match line {
+                Line::Header(node_count) => {
+                    out_degrees.resize(out_degrees.len().max(node_count), 0);
+                }
+                Line::Edge(from, to) => {
+                    out_degrees.resize(out_degrees.len().max(from).max(to), 0);
+                    out_degrees[from - 1] += 1;
+                }
+                Line::Empty => {}
+            }
+        })?;
+
+        let mut graph = DirectedGraph { nodes: Vec::with_capacity(out_degrees.len()) };
+        for (index, out_degree) in out_degrees.into_iter().enumerate() {
+            let mut node = Node { adjacent_node_ids: Vec::with_capacity(out_degree), ..Node::default() };
+            node.meta.node_id = index + 1;
+            graph.nodes.push(node);
+        }
+        for_each_line(&path, |line| {
+            if let Line::Edge(from, to) = line {
+                graph.nodes[from - 1].connect_to(to)
+            }
+        })?;
+        Ok(graph)
+    }
+}
+
+fn for_each_line<P: AsRef<Path>, F: FnMut(Line)>(path: P, mut action: F) -> Result<(), LoadError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buffer = String::new();
+    let mut parser = LineParser::default();
+    while reader.read_line(&mut buffer)? > 0 {
+        action(parser.parse(&buffer)?);
+        buffer.clear();
+    }
+    Ok(())
+}
+
+// numbers the lines and remembers whether the header may still come
+#[derive(Default)]
+struct LineParser {
+    line_number: usize,
+    seen_content: bool,
+    node_count: Option<usize>,
+}
+
+impl LineParser {
+    fn parse(&mut self, text: &str) -> Result<Line, LoadError> {
+        self.line_number += 1;
+        let line_number = self.line_number;
+        let error = |message: String| LoadError::Parse { line: line_number, message };
+        let max_node_id = self.node_count.unwrap_or(MAX_NODE_ID_WITHOUT_HEADER);
+        let parse_id = |token: &str| match token.parse::<usize>() {
+            Ok(0) => Err(error("node ids start from 1".to_string())),
+            Ok(id) if id > max_node_id => match self.node_count {
+                Some(node_count) => Err(error(format!("node id {} exceeds the node count {}", id, node_count))),
+                None => Err(error(format!("node id {} needs a node count header", id))),
+            },
+            Ok(id) => Ok(id),
+            Err(e) => Err(error(format!("invalid node id {:?}: {}", token, e))),
+        };
+
+        let tokens = text.split_whitespace().collect::<Vec<&str>>();
+        let line = match tokens.as_slice() {
+            [] => return Ok(Line::Empty),
+            [first, ..] if first.starts_with('#') => return Ok(Line::Empty),
+            [node_count] if !self.seen_content => node_count.parse::<usize>()
+                .map(Line::Header)
+                .map_err(|e| error(format!("invalid node count {:?}: {}", node_count, e)))?,
+            [from, to] => Line::Edge(parse_id(from)?, parse_id(to)?),
+            _ => return Err(error(format!("expected `from to` pair, got {:?}", text.trim_end()))),
+        };
+        if let Line::Header(node_count) = line {
+            self.node_count = Some(node_count);
+        }
+        self.seen_content = true;
+        Ok(line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::{DirectedGraph, loader::LoadError};
+
+    fn adjacency(graph: &DirectedGraph) -> Vec<Vec<usize>> {
+        graph.nodes.iter().map(|node| node.adjacent_node_ids.clone()).collect()
+    }
+
+    #[test]
+    fn same_graph_as_matrix_loading() {
+        let expected = DirectedGraph::from(utils::read_matrix(File::open("./test_data/5.txt").unwrap()));
+
+        let streamed = DirectedGraph::from_edge_list(&include_bytes!("../test_data/5.txt")[..]).unwrap();
+        let two_pass = DirectedGraph::from_edge_list_file("./test_data/5.txt").unwrap();
+
+        assert_eq!(adjacency(&streamed), adjacency(&expected));
+        assert_eq!(adjacency(&two_pass), adjacency(&expected));
+        assert_eq!(two_pass.nodes[11].meta.node_id, 12);
+    }
+
+    #[test]
+    fn header_allocates_isolated_nodes() {
+        let graph = DirectedGraph::from_edge_list("6\n1 2\n\n2 3\n".as_bytes()).unwrap();
+
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.strongly_connected_components().count(), 6);
+    }
+
+    #[test]
+    fn header_after_comments() {
+        for graph in [
+            DirectedGraph::from_edge_list(&include_bytes!("../test_data/commented.txt")[..]).unwrap(),
+            DirectedGraph::from_edge_list_file("./test_data/commented.txt").unwrap(),
+        ] {
+            assert_eq!(graph.node_count(), 4);
+            assert_eq!(adjacency(&graph), vec![vec![2], vec![3], vec![1], vec![]]);
+        }
+    }
+
+    #[test]
+    fn rejects_ids_beyond_node_count() {
+        let line_of = |result: Result<DirectedGraph, LoadError>| match result {
+            Err(LoadError::Parse { line, .. }) => line,
+            other => panic!("unexpected result {:?}", other),
+        };
+
+        assert_eq!(line_of(DirectedGraph::from_edge_list("3\n1 2\n2 4\n".as_bytes())), 3);
+        assert_eq!(line_of(DirectedGraph::from_edge_list(&include_bytes!("../test_data/sparse_id.txt")[..])), 2);
+        assert_eq!(line_of(DirectedGraph::from_edge_list_file("./test_data/sparse_id.txt")), 2);
+    }
+
+    #[test]
+    fn reports_line_of_malformed_input() {
+        let errors = ["1 2\n2 x\n", "1 2\n\n3 0\n", "1 2\n2 3 4\n", "1 2\n5\n"]
+            .iter()
+            .map(|text| match DirectedGraph::from_edge_list(text.as_bytes()) {
+                Err(LoadError::Parse { line, .. }) => line,
+                other => panic!("unexpected result {:?}", other),
+            })
+            .collect::<Vec<usize>>();
+
+        assert_eq!(errors, vec![2, 3, 2, 2]);
+        assert!(matches!(DirectedGraph::from_edge_list_file("./test_data/missing.txt"), Err(LoadError::Io(_))));
+    }
+}
@@ -0,0 +1,277 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering::Relaxed},
+        Condvar,
+        Mutex,
+    },
+    thread,
+};
+
+use crate::{Components, DirectedGraph, csr::CsrGraph};
+
+// tasks with fewer nodes run their forward and backward searches on one thread
+const PARALLEL_SEARCH_THRESHOLD: usize = 50_000;
+
+impl DirectedGraph {
+    /// See [`CsrGraph::parallel_strongly_connected_components`]
+    pub fn parallel_strongly_connected_components(&self, threads: usize) -> Components {
+        CsrGraph::from(self).parallel_strongly_connected_components(threads)
+    }
+}
+
+impl CsrGraph {
+    /// Forward-backward decomposition with trimming on a pool of `threads` workers.
+    /// Gives the same components as [`CsrGraph::strongly_connected_components`],
+    /// but component ids are in no particular order.
+    pub fn parallel_strongly_connected_components(&self, threads: usize) -> Components {
+        let node_count = self.node_count();
+        let backward = self.reversed();
+        let shared = Shared {
+            forward: self,
+            backward: &backward,
+            colour: atomic_vec(node_count, FIRST_COLOUR),
+            forward_mark: atomic_vec(node_count, 0),
+            backward_mark: atomic_vec(node_count, 0),
+            in_degree: atomic_vec(node_count, 0),
+            out_degree: atomic_vec(node_count, 0),
+            membership: atomic_vec(node_count, 0),
+            component_counter: AtomicUsize::new(0),
+            colour_counter: AtomicUsize::new(FIRST_COLOUR),
+            queue: Mutex::new(Queue { tasks: Vec::new(), pending: 0 }),
+            ready: Condvar::new(),
+        };
+        if node_count > 0 {
+            let task = Task { colour: FIRST_COLOUR, nodes: (1..=node_count).collect() };
+            shared.queue.lock().unwrap().push(vec![task]);
+            thread::scope(|scope| {
+                for _ in 0..threads.max(1) {
+                    scope.spawn(|| shared.work());
+                }
+            });
+        }
+        Components::from_membership(shared.membership.into_iter().map(AtomicUsize::into_inner).collect())
+    }
+}
+
+// colour of nodes already assigned to a component
+const DONE: usize = 0;
+const FIRST_COLOUR: usize = 1;
+
+// nodes of one colour, a strongly connected component never spans several colours
+struct Task {
+    colour: usize,
+    nodes: Vec<usize>,
+}
+
+struct Queue {
+    tasks: Vec<Task>,
+    // tasks queued or being processed
+    pending: usize,
+}
+
+impl Queue {
+    fn push(&mut self, tasks: Vec<Task>) {
+        self.pending += tasks.len();
+        self.tasks.extend(tasks);
+    }
+}
+
+// every node belongs to exactly one task at a time, so relaxed per-node updates don't race
+struct Shared<'a> {
+    forward: &'a CsrGraph,
+    backward: &'a CsrGraph,
+    colour: Vec<AtomicUsize>,
+    // colour of the last task whose forward (backward) search reached the node
+    forward_mark: Vec<AtomicUsize>,
+    backward_mark: Vec<AtomicUsize>,
+    // degrees inside the node's colour, used by trimming
+    in_degree: Vec<AtomicUsize>,
+    out_degree: Vec<AtomicUsize>,
+    membership: Vec<AtomicUsize>,
+    component_counter: AtomicUsize,
+    colour_counter: AtomicUsize,
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+impl Shared<'_> {
+    fn work(&self) {
+        loop {
+            let task = {
+                let mut queue = self.queue.lock().unwrap();
+                loop {
+                    if let Some(task) = queue.tasks.pop() {
+                        break Some(task);
+                    }
+                    if queue.pending == 0 {
+                        break None;
+                    }
+                    queue = self.ready.wait(queue).unwrap();
+                }
+            };
+            let Some(task) = task else {
+                self.ready.notify_all();
+                return;
+            };
+            let subtasks = self.process(task);
+            let mut queue = self.queue.lock().unwrap();
+            queue.push(subtasks);
+            queue.pending -= 1;
+            self.ready.notify_all();
+        }
+    }
+
+    fn process(&self, task: Task) -> Vec<Task> {
+        let colour = task.colour;
+        self.trim(&task);
+        let Some(pivot) = task.nodes.iter().copied().find(|node_id| self.colour_of(*node_id) == colour) else {
+            return Vec::new();
+        };
+
+        if task.nodes.len() >= PARALLEL_SEARCH_THRESHOLD {
+            thread::scope(|scope| {
+                scope.spawn(|| self.reach(self.forward, pivot, colour, &self.forward_mark));
+                self.reach(self.backward, pivot, colour, &self.backward_mark);
+            });
+        } else {
+            self.reach(self.forward, pivot, colour, &self.forward_mark);
+            self.reach(self.backward, pivot, colour, &self.backward_mark);
+        }
+
+        // reached both ways: the pivot's component; one way: a new colour each; rest keeps the colour
+        let component_id = self.component_counter.fetch_add(1, Relaxed) + 1;
+        let forward_colour = self.colour_counter.fetch_add(2, Relaxed) + 1;
+        let backward_colour = forward_colour + 1;
+        let mut forward_only = Vec::new();
+        let mut backward_only = Vec::new();
+        let mut rest = Vec::new();
+        for node_id in task.nodes {
+            if self.colour_of(node_id) != colour {
+                continue;
+            }
+            let forward = self.forward_mark[node_id - 1].load(Relaxed) == colour;
+            let backward = self.backward_mark[node_id - 1].load(Relaxed) == colour;
+            match (forward, backward) {
+                (true, true) => self.assign(node_id, component_id),
+                (true, false) => {
+                    self.colour[node_id - 1].store(forward_colour, Relaxed);
+                    forward_only.push(node_id);
+                }
+                (false, true) => {
+                    self.colour[node_id - 1].store(backward_colour, Relaxed);
+                    backward_only.push(node_id);
+                }
+                (false, false) => rest.push(node_id),
+            }
+        }
+        [(forward_colour, forward_only), (backward_colour, backward_only), (colour, rest)]
+            .into_iter()
+            .filter(|(_, nodes)| !nodes.is_empty())
+            .map(|(colour, nodes)| Task { colour, nodes })
+            .collect()
+    }
+
+    // nodes without incoming or outgoing edges inside the colour are components on their own
+    fn trim(&self, task: &Task) {
+        let colour = task.colour;
+        let mut trimmed = Vec::new();
+        for node_id in task.nodes.iter().copied() {
+            let in_degree = self.degree_within(self.backward, node_id, colour);
+            let out_degree = self.degree_within(self.forward, node_id, colour);
+            self.in_degree[node_id - 1].store(in_degree, Relaxed);
+            self.out_degree[node_id - 1].store(out_degree, Relaxed);
+            if in_degree == 0 || out_degree == 0 {
+                trimmed.push(node_id);
+            }
+        }
+        while let Some(node_id) = trimmed.pop() {
+            if self.colour_of(node_id) != colour {
+                continue;
+            }
+            let component_id = self.component_counter.fetch_add(1, Relaxed) + 1;
+            self.assign(node_id, component_id);
+            for (graph, degrees) in [(self.forward, &self.in_degree), (self.backward, &self.out_degree)] {
+                for adjacent_node_id in graph.adjacent_node_ids(node_id) {
+                    if self.colour_of(*adjacent_node_id) == colour
+                        && degrees[adjacent_node_id - 1].fetch_sub(1, Relaxed) == 1 {
+                        trimmed.push(*adjacent_node_id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn degree_within(&self, graph: &CsrGraph, node_id: usize, colour: usize) -> usize {
+        graph.adjacent_node_ids(node_id).iter()
+            .filter(|adjacent_node_id| self.colour_of(**adjacent_node_id) == colour)
+            .count()
+    }
+
+    fn reach(&self, graph: &CsrGraph, pivot: usize, colour: usize, mark: &[AtomicUsize]) {
+        mark[pivot - 1].store(colour, Relaxed);
+        let mut stack = vec![pivot];
+        while let Some(node_id) = stack.pop() {
+            for adjacent_node_id in graph.adjacent_node_ids(node_id) {
+                if self.colour_of(*adjacent_node_id) == colour && mark[adjacent_node_id - 1].load(Relaxed) != colour {
+                    mark[adjacent_node_id - 1].store(colour, Relaxed);
+                    stack.push(*adjacent_node_id);
+                }
+            }
+        }
+    }
+
+    fn colour_of(&self, node_id: usize) -> usize {
+        self.colour[node_id - 1].load(Relaxed)
+    }
+
+    fn assign(&self, node_id: usize, component_id: usize) {
+        self.colour[node_id - 1].store(DONE, Relaxed);
+        self.membership[node_id - 1].store(component_id, Relaxed);
+    }
+}
+
+fn atomic_vec(len: usize, value: usize) -> Vec<AtomicUsize> {
+    (0..len).map(|_| AtomicUsize::new(value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::{Components, DirectedGraph, csr::CsrGraph};
+
+    fn partition(components: &Components) -> Vec<Vec<usize>> {
+        let mut members = components.members();
+        members.sort();
+        members
+    }
+
+    #[test]
+    fn same_components_as_kosaraju_on_test_data() {
+        for path in ["./test_data/1.txt", "./test_data/2.txt", "./test_data/3.txt",
+            "./test_data/4.txt", "./test_data/5.txt"] {
+            let graph = DirectedGraph::from(utils::read_matrix(File::open(path).unwrap()));
+
+            assert_eq!(partition(&graph.parallel_strongly_connected_components(4)),
+                       partition(&graph.strongly_connected_components()), "{}", path);
+        }
+    }
+
+    #[test]
+    fn same_components_on_random_graphs() {
+        let mut rng = XorShift64::new(2024);
+        for round in 0..40 {
+            let node_count = 1 + rng.below(3000);
+            let edge_count = rng.below(3 * node_count);
+            let edges = (0..edge_count)
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count)))
+                .collect::<Vec<(usize, usize)>>();
+            let graph = CsrGraph::from_edges(node_count, &edges);
+
+            assert_eq!(partition(&graph.parallel_strongly_connected_components(1 + round % 4)),
+                       partition(&graph.strongly_connected_components()));
+        }
+    }
+}
@@ -0,0 +1,325 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{Components, DirectedGraph};
+
+// 64 MB of closure bitsets, components past the budget are answered by search
+const CLOSURE_WORD_LIMIT: usize = 1 << 23;
+// 8 MB of memoised search results, dropped all at once when full
+const SEARCH_CACHE_WORD_LIMIT: usize = 1 << 20;
+
+/// Answers "can a reach b?" queries: every strongly connected component keeps a bitset of
+/// the components it reaches in the condensation.
+///
+/// Components are numbered in reverse topological order, so a component only reaches
+/// components with smaller ids, and every bitset is trimmed to the span of its set words.
+/// A full closure takes up to n^2 / 16 bytes (about 625 MB for a 100k node chain), so the
+/// bitsets stop at a word budget: components past it are answered by a search over the
+/// condensation that ends at the first components with a bitset. The search costs up to
+/// O(V + E) the first time a component is queried, its result is memoised for later queries
+/// while the memo stays within its own budget; see [`ReachabilityIndex::is_fully_indexed`].
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    components: Components,
+    // node ids of every component, indexed by component id - 1
+    members: Vec<Vec<usize>>,
+    // components reachable from every component (itself included), indexed by component id - 1;
+    // covers the components from 1 up to the word budget
+    closure: Vec<ComponentSet>,
+    condensation: DirectedGraph,
+    // reversed condensation, only kept when the closure doesn't cover every component
+    predecessors: Option<DirectedGraph>,
+    searched: RefCell<SearchCache>,
+}
+
+// reachable sets of the components past the closure that were searched already
+#[derive(Debug, Clone, Default)]
+struct SearchCache {
+    sets: HashMap<usize, Rc<ComponentSet>>,
+    word_count: usize,
+}
+
+impl DirectedGraph {
+    pub fn reachability_index(&self) -> ReachabilityIndex {
+        ReachabilityIndex::new(self)
+    }
+}
+
+impl ReachabilityIndex {
+    /// O(V + E) plus the closure, which stops at 64 MB of bitsets
+    pub fn new(graph: &DirectedGraph) -> Self {
+        Self::with_word_limit(graph, CLOSURE_WORD_LIMIT)
+    }
+
+    /// Keeps at most `word_limit` 64-bit words of closure bitsets,
+    /// queries from the components past them search the condensation
+    pub fn with_word_limit(graph: &DirectedGraph, word_limit: usize) -> Self {
+        let condensation = graph.condensation();
+        let mut closure: Vec<ComponentSet> = Vec::new();
+        let mut word_count = 0;
+        // successors have smaller ids, so their sets are ready
+        for component_id in 1..=condensation.components.count() {
+            let node = &condensation.graph.nodes[component_id - 1];
+            let successors = node.adjacent_node_ids.iter().map(|successor| &closure[successor - 1]);
+            let set = ComponentSet::union_with(component_id, successors);
+            word_count += set.words.len();
+            if word_count > word_limit {
+                break;
+            }
+            closure.push(set);
+        }
+        let predecessors = (closure.len() < condensation.components.count())
+            .then(|| condensation.graph.reversed());
+        ReachabilityIndex {
+            members: condensation.components.members(),
+            components: condensation.components,
+            closure,
+            condensation: condensation.graph,
+            predecessors,
+            searched: RefCell::default(),
+        }
+    }
+
+    /// True if every component has its closure bitset, so that every query is answered
+    /// from the index; otherwise some queries search the condensation
+    pub fn is_fully_indexed(&self) -> bool {
+        self.predecessors.is_none()
+    }
+
+    /// True if there is a path from `a` to `b`, every node reaches itself.
+    /// Constant time for indexed components; for the others the first query from
+    /// a component costs a search of the condensation, later ones reuse its result.
+    pub fn reaches(&self, a: usize, b: usize) -> bool {
+        let from = self.components.component_of(a);
+        let to = self.components.component_of(b);
+        if from < to {
+            return false;
+        }
+        match self.closure.get(from - 1) {
+            Some(set) => set.contains(to),
+            None => self.searched_set(from).contains(to),
+        }
+    }
+
+    /// Nodes reachable from `a` except `a` itself, ascending
+    pub fn descendants(&self, a: usize) -> Vec<usize> {
+        let from = self.components.component_of(a);
+        let reachable = match self.closure.get(from - 1) {
+            Some(set) => set.iter().collect::<Vec<usize>>(),
+            None => self.searched_set(from).iter().collect(),
+        };
+        let mut descendants = reachable.into_iter()
+            .flat_map(|component_id| self.members[component_id - 1].iter().copied())
+            .filter(|node_id| *node_id != a)
+            .collect::<Vec<usize>>();
+        descendants.sort_unstable();
+        descendants
+    }
+
+    /// Nodes that reach `a` except `a` itself, ascending;
+    /// searches the reversed condensation unless fully indexed
+    pub fn ancestors(&self, a: usize) -> Vec<usize> {
+        let to = self.components.component_of(a);
+        let ancestor_components = match &self.predecessors {
+            None => (to..=self.closure.len())
+                .filter(|component_id| self.closure[component_id - 1].contains(to))
+                .collect(),
+            Some(predecessors) => depth_first(predecessors, to, |_| true),
+        };
+        let mut ancestors = ancestor_components.into_iter()
+            .flat_map(|component_id| self.members[component_id - 1].iter().copied())
+            .filter(|node_id| *node_id != a)
+            .collect::<Vec<usize>>();
+        ancestors.sort_unstable();
+        ancestors
+    }
+
+    // components reachable from a component past the closure, memoised
+    fn searched_set(&self, from: usize) -> Rc<ComponentSet> {
+        if let Some(set) = self.searched.borrow().sets.get(&from) {
+            return Rc::clone(set);
+        }
+        let set = Rc::new(ComponentSet::from_ascending(&self.search(from)));
+        let mut cache = self.searched.borrow_mut();
+        if cache.word_count + set.words.len() > SEARCH_CACHE_WORD_LIMIT {
+            cache.sets.clear();
+            cache.word_count = 0;
+        }
+        cache.word_count += set.words.len();
+        cache.sets.insert(from, Rc::clone(&set));
+        set
+    }
+
+    // components reachable from `from`, itself included, ascending;
+    // the search stops at the components with a bitset
+    fn search(&self, from: usize) -> Vec<usize> {
+        let covered = self.closure.len();
+        let mut reachable = vec![false; from];
+        for component_id in depth_first(&self.condensation, from, |component_id| component_id > covered) {
+            reachable[component_id - 1] = true;
+            if component_id <= covered {
+                self.closure[component_id - 1].iter().for_each(|reached| reachable[reached - 1] = true);
+            }
+        }
+        (1..=from).filter(|component_id| reachable[component_id - 1]).collect()
+    }
+}
+
+// components reached from `start` in the condensation, only expanding those for which `expand` holds
+fn depth_first<F: Fn(usize) -> bool>(graph: &DirectedGraph, start: usize, expand: F) -> Vec<usize> {
+    let mut visited = vec![false; graph.node_count()];
+    let mut reached = vec![start];
+    let mut stack = vec![start];
+    visited[start - 1] = true;
+    while let Some(component_id) = stack.pop() {
+        if !expand(component_id) {
+            continue;
+        }
+        for adjacent_id in graph.nodes[component_id - 1].adjacent_node_ids.iter().copied() {
+            if !visited[adjacent_id - 1] {
+                visited[adjacent_id - 1] = true;
+                reached.push(adjacent_id);
+                stack.push(adjacent_id);
+            }
+        }
+    }
+    reached
+}
+
+// bitset of component ids trimmed to its non-zero words, bit i stands for component i + 1
+#[derive(Debug, Clone)]
+struct ComponentSet {
+    first_word: usize,
+    words: Vec<u64>,
+}
+
+impl ComponentSet {
+    fn from_ascending(component_ids: &[usize]) -> Self {
+        let first_word = (component_ids[0] - 1) / 64;
+        let mut words = vec![0_u64; (component_ids[component_ids.len() - 1] - 1) / 64 - first_word + 1];
+        for component_id in component_ids {
+            words[(component_id - 1) / 64 - first_word] |= 1 << ((component_id - 1) % 64);
+        }
+        ComponentSet { first_word, words }
+    }
+
+    fn union_with<'a, I: Iterator<Item=&'a ComponentSet> + Clone>(component_id: usize, others: I) -> Self {
+        let own_word = (component_id - 1) / 64;
+        let first_word = others.clone().map(|set| set.first_word).min().unwrap_or(own_word).min(own_word);
+        let last_word = others.clone()
+            .map(|set| set.first_word + set.words.len() - 1)
+            .max()
+            .unwrap_or(own_word)
+            .max(own_word);
+
+        let mut words = vec![0_u64; last_word - first_word + 1];
+        words[own_word - first_word] |= 1 << ((component_id - 1) % 64);
+        for set in others {
+            let offset = set.first_word - first_word;
+            for (index, word) in set.words.iter().enumerate() {
+                words[offset + index] |= word;
+            }
+        }
+        ComponentSet { first_word, words }
+    }
+
+    fn contains(&self, component_id: usize) -> bool {
+        let word = (component_id - 1) / 64;
+        word >= self.first_word
+            && word < self.first_word + self.words.len()
+            && self.words[word - self.first_word] & (1 << ((component_id - 1) % 64)) != 0
+    }
+
+    fn iter(&self) -> impl Iterator<Item=usize> + '_ {
+        self.words.iter().enumerate().flat_map(move |(index, word)| {
+            let base = (self.first_word + index) * 64;
+            (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| base + bit + 1)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::{DirectedGraph, reachability::ReachabilityIndex};
+
+    #[test]
+    fn reachability_across_components() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/3.txt").unwrap()));
+        let index = graph.reachability_index();
+        assert!(index.is_fully_indexed());
+
+        assert!(index.reaches(1, 3));
+        assert!(index.reaches(3, 1));
+        assert!(index.reaches(7, 4));
+        assert!(index.reaches(5, 5));
+        assert!(!index.reaches(4, 1));
+        assert!(!index.reaches(1, 6));
+        assert_eq!(index.descendants(1), vec![2, 3, 4]);
+        assert_eq!(index.descendants(4), vec![]);
+        assert_eq!(index.ancestors(4), vec![1, 2, 3, 5, 6, 7, 8]);
+        assert_eq!(index.ancestors(6), vec![7, 8]);
+    }
+
+    #[test]
+    fn matches_traversal_on_long_chain_with_shortcuts() {
+        // more than 64 components, so sets span several words
+        let node_count = 300;
+        let mut graph = DirectedGraph::with_node_count(node_count);
+        for node_id in 1..node_count {
+            if node_id % 7 != 0 {
+                graph.add_edge(node_id, node_id + 1);
+            }
+            if node_id + 70 <= node_count && node_id % 5 == 0 {
+                graph.add_edge(node_id, node_id + 70);
+            }
+        }
+        graph.add_edge(147, 141);
+        let reversed = graph.reversed();
+
+        // full closure, bitsets for some of the components and none at all
+        for word_limit in [usize::MAX, 500, 0] {
+            let index = ReachabilityIndex::with_word_limit(&graph, word_limit);
+            assert_eq!(index.is_fully_indexed(), word_limit == usize::MAX);
+            for from in 1..=node_count {
+                let mut expected = graph_core::traversal::breadth_first(&graph, from);
+                expected.retain(|node_id| *node_id != from);
+                expected.sort();
+                assert_eq!(index.descendants(from), expected, "descendants of {}", from);
+                for to in 1..=node_count {
+                    assert_eq!(index.reaches(from, to), from == to || expected.binary_search(&to).is_ok());
+                }
+
+                let mut expected = graph_core::traversal::breadth_first(&reversed, from);
+                expected.retain(|node_id| *node_id != from);
+                expected.sort();
+                assert_eq!(index.ancestors(from), expected, "ancestors of {}", from);
+            }
+            assert!(index.reaches(141, 147) && index.reaches(147, 141));
+            assert!(!index.reaches(140, 141));
+        }
+    }
+
+    #[test]
+    fn long_chain_stays_within_word_budget() {
+        let node_count = 100_000;
+        let mut graph = DirectedGraph::with_node_count(node_count);
+        for node_id in 1..node_count {
+            graph.add_edge(node_id, node_id + 1);
+        }
+        let index = graph.reachability_index();
+        assert!(!index.is_fully_indexed());
+
+        // the second round reuses the searched sets
+        for _ in 0..2 {
+            assert!(index.reaches(1, node_count));
+            assert!(index.reaches(2, 3) && !index.reaches(3, 2));
+        }
+        assert!(!index.reaches(node_count, 1));
+        assert!(index.reaches(50_000, 50_001));
+        assert_eq!(index.descendants(1).len(), node_count - 1);
+        assert_eq!(index.descendants(node_count - 2), vec![node_count - 1, node_count]);
+        assert_eq!(index.ancestors(3), vec![1, 2]);
+        assert_eq!(index.ancestors(node_count).len(), node_count - 1);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{DirectedGraph, topo::Cycle};
+
+impl DirectedGraph {
+    /// Minimal subgraph with the same reachability: an edge `u -> v` is dropped when `v`
+    /// is also reachable through another successor of `u`, parallel edges are merged.
+    /// Defined only for acyclic graphs, for cyclic ones reduce the condensation graph instead.
+    pub fn transitive_reduction(&self) -> Result<DirectedGraph, Cycle> {
+        let order = self.topological_order()?;
+        let mut position = vec![0_usize; self.nodes.len()];
+        for (index, node_id) in order.iter().enumerate() {
+            position[node_id - 1] = index;
+        }
+        let index = self.reachability_index();
+
+        let mut reduced = DirectedGraph::with_node_count(self.nodes.len());
+        for node in self.nodes.iter() {
+            let mut successors = node.adjacent_node_ids.clone();
+            successors.sort_unstable_by_key(|node_id| position[node_id - 1]);
+            successors.dedup();
+
+            // a successor can only be reached through successors earlier in topological order
+            let mut kept: Vec<usize> = Vec::new();
+            for successor in successors {
+                if !kept.iter().any(|earlier| index.reaches(*earlier, successor)) {
+                    kept.push(successor);
+                }
+            }
+            for successor in node.adjacent_node_ids.iter() {
+                if let Some(slot) = kept.iter().position(|id| id == successor) {
+                    reduced.add_edge(node.meta.node_id, kept.swap_remove(slot));
+                }
+            }
+        }
+        Ok(reduced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::DirectedGraph;
+
+    fn edges(graph: &DirectedGraph) -> Vec<(usize, usize)> {
+        graph.nodes.iter()
+            .flat_map(|node| {
+                node.adjacent_node_ids.iter().map(|to| (node.meta.node_id, *to)).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn removes_shortcuts_and_duplicates() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(1, 4);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(2, 4);
+        graph.add_edge(5, 4);
+        graph.add_edge(5, 4);
+        graph.add_edge(6, 5);
+
+        let reduced = graph.transitive_reduction().unwrap();
+        assert_eq!(edges(&reduced), vec![(1, 2), (2, 3), (3, 4), (5, 4), (6, 5)]);
+
+        let original = graph.reachability_index();
+        let after = reduced.reachability_index();
+        for a in 1..=6 {
+            assert_eq!(original.descendants(a), after.descendants(a));
+        }
+    }
+
+    #[test]
+    fn rejects_cyclic_graph() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/2.txt").unwrap()));
+
+        assert!(graph.transitive_reduction().is_err());
+        assert!(graph.condensation().graph.transitive_reduction().is_ok());
+    }
+}
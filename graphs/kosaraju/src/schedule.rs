@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use crate::{DirectedGraph, topo::Cycle};
+
+/// How long tasks take when an acyclic graph is read as a schedule:
+/// nodes are tasks and an edge `u -> v` means `v` starts after `u` is finished
+#[derive(Debug, Clone)]
+pub enum Durations {
+    /// every edge takes one step and tasks take no time, so the longest path counts edges
+    Unit,
+    /// duration of every task, indexed by node_id - 1; edges take no time
+    PerNode(Vec<usize>),
+    /// duration of every `(from, to)` edge, missing edges and tasks take no time
+    PerEdge(HashMap<(usize, usize), usize>),
+}
+
+impl Durations {
+    fn of_node(&self, node_id: usize) -> usize {
+        match self {
+            Durations::PerNode(durations) => durations[node_id - 1],
+            _ => 0,
+        }
+    }
+
+    fn of_edge(&self, from: usize, to: usize) -> usize {
+        match self {
+            Durations::Unit => 1,
+            Durations::PerNode(_) => 0,
+            Durations::PerEdge(durations) => durations.get(&(from, to)).copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Earliest and latest start of every task, indexed by node_id - 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// length of the longest path, the shortest time to finish all tasks
+    pub length: usize,
+    pub earliest_start: Vec<usize>,
+    pub latest_start: Vec<usize>,
+    /// longest path from a task without predecessors to a task finishing last
+    pub critical_path: Vec<usize>,
+}
+
+impl Schedule {
+    /// How much a task can be delayed without delaying the whole schedule
+    pub fn slack(&self, node_id: usize) -> usize {
+        self.latest_start[node_id - 1] - self.earliest_start[node_id - 1]
+    }
+}
+
+impl DirectedGraph {
+    /// Critical path method over the topological order, fails on cyclic graphs
+    pub fn critical_path(&self, durations: &Durations) -> Result<Schedule, Cycle> {
+        let order = self.topological_order()?;
+        let reversed = self.reversed();
+        let node_count = self.nodes.len();
+
+        let mut earliest_start = vec![0_usize; node_count];
+        for node_id in order.iter() {
+            let finish = earliest_start[node_id - 1] + durations.of_node(*node_id);
+            for successor in &self.nodes[node_id - 1].adjacent_node_ids {
+                let start = finish + durations.of_edge(*node_id, *successor);
+                earliest_start[successor - 1] = earliest_start[successor - 1].max(start);
+            }
+        }
+        let finish = |node_id: usize| earliest_start[node_id - 1] + durations.of_node(node_id);
+        let length = (1..=node_count).map(finish).max().unwrap_or(0);
+
+        let mut latest_start = vec![0_usize; node_count];
+        for node_id in order.iter().rev() {
+            let latest_finish = self.nodes[node_id - 1].adjacent_node_ids.iter()
+                .map(|successor| latest_start[successor - 1] - durations.of_edge(*node_id, *successor))
+                .min()
+                .unwrap_or(length);
+            latest_start[node_id - 1] = latest_finish - durations.of_node(*node_id);
+        }
+
+        // walk back from the first task finishing last through tight predecessors
+        let mut critical_path = Vec::new();
+        let mut current = (1..=node_count).find(|node_id| finish(*node_id) == length);
+        while let Some(node_id) = current {
+            critical_path.push(node_id);
+            current = reversed.nodes[node_id - 1].adjacent_node_ids.iter()
+                .copied()
+                .filter(|predecessor| {
+                    finish(*predecessor) + durations.of_edge(*predecessor, node_id) == earliest_start[node_id - 1]
+                })
+                .min();
+        }
+        critical_path.reverse();
+
+        Ok(Schedule { length, earliest_start, latest_start, critical_path })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{DirectedGraph, schedule::Durations};
+
+    // 1 -> 2 -> 4, 1 -> 3 -> 4, 5 is independent
+    fn diamond() -> DirectedGraph {
+        let mut graph = DirectedGraph::with_node_count(5);
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 4);
+        graph.add_edge(3, 4);
+        graph
+    }
+
+    #[test]
+    fn task_durations() {
+        let schedule = diamond().critical_path(&Durations::PerNode(vec![2, 3, 5, 1, 4])).unwrap();
+
+        assert_eq!(schedule.length, 8);
+        assert_eq!(schedule.earliest_start, vec![0, 2, 2, 7, 0]);
+        assert_eq!(schedule.latest_start, vec![0, 4, 2, 7, 4]);
+        assert_eq!(schedule.critical_path, vec![1, 3, 4]);
+        assert_eq!(schedule.slack(2), 2);
+        assert_eq!(schedule.slack(5), 4);
+    }
+
+    #[test]
+    fn edge_durations() {
+        let durations = HashMap::from([((1, 2), 4), ((1, 3), 1), ((2, 4), 1), ((3, 4), 3)]);
+        let schedule = diamond().critical_path(&Durations::PerEdge(durations)).unwrap();
+
+        assert_eq!(schedule.length, 5);
+        assert_eq!(schedule.critical_path, vec![1, 2, 4]);
+        assert_eq!(schedule.slack(3), 1);
+    }
+
+    #[test]
+    fn unit_durations_count_edges() {
+        let mut graph = diamond();
+        graph.add_edge(4, 5);
+        graph.add_edge(1, 5);
+        let schedule = graph.critical_path(&Durations::Unit).unwrap();
+
+        assert_eq!(schedule.length, 3);
+        assert_eq!(schedule.critical_path, vec![1, 2, 4, 5]);
+        graph.add_edge(5, 1);
+        assert!(graph.critical_path(&Durations::Unit).is_err());
+    }
+}
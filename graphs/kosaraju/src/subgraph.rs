@@ -0,0 +1,160 @@
+use crate::DirectedGraph;
+
+/// Graph over a subset of nodes, renumbered from 1 in the order they were selected
+#[derive(Debug, Clone)]
+pub struct Subgraph {
+    pub graph: DirectedGraph,
+    /// node id in the source graph of every subgraph node, indexed by node_id - 1
+    pub original_ids: Vec<usize>,
+}
+
+impl Subgraph {
+    pub fn original_id(&self, node_id: usize) -> usize {
+        self.original_ids[node_id - 1]
+    }
+}
+
+impl DirectedGraph {
+    /// Removes one `from -> to` edge, parallel edges are removed one at a time.
+    /// Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> bool {
+        let Some(node) = self.nodes.get_mut(from.wrapping_sub(1)) else {
+            return false;
+        };
+        match node.adjacent_node_ids.iter().position(|adjacent_node_id| *adjacent_node_id == to) {
+            Some(index) => {
+                node.adjacent_node_ids.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the node with all its edges; ids stay dense, so every node
+    /// with a larger id moves down by one
+    pub fn remove_vertex(&mut self, node_id: usize) {
+        assert!(node_id > 0 && node_id <= self.nodes.len(), "Node {} doesn't exist", node_id);
+        self.nodes.remove(node_id - 1);
+        for node in self.nodes.iter_mut() {
+            if node.meta.node_id > node_id {
+                node.meta.node_id -= 1;
+            }
+            node.adjacent_node_ids.retain(|adjacent_node_id| *adjacent_node_id != node_id);
+            node.adjacent_node_ids.iter_mut()
+                .filter(|adjacent_node_id| **adjacent_node_id > node_id)
+                .for_each(|adjacent_node_id| *adjacent_node_id -= 1);
+        }
+    }
+
+    /// The given nodes with the edges between them; repeated ids are kept once
+    pub fn induced_subgraph(&self, node_ids: &[usize]) -> Subgraph {
+        let mut new_ids = vec![0; self.nodes.len()];
+        let mut original_ids = Vec::with_capacity(node_ids.len());
+        for node_id in node_ids {
+            if new_ids[node_id - 1] == 0 {
+                original_ids.push(*node_id);
+                new_ids[node_id - 1] = original_ids.len();
+            }
+        }
+        let mut graph = DirectedGraph::with_node_count(original_ids.len());
+        for (index, node_id) in original_ids.iter().enumerate() {
+            for adjacent_node_id in self.nodes[node_id - 1].adjacent_node_ids.iter() {
+                if new_ids[adjacent_node_id - 1] != 0 {
+                    graph.add_edge(index + 1, new_ids[adjacent_node_id - 1]);
+                }
+            }
+        }
+        Subgraph { graph, original_ids }
+    }
+
+    /// Same nodes, only the edges for which `predicate(from, to)` holds
+    pub fn filter_edges<P>(&self, predicate: P) -> DirectedGraph
+        where P: Fn(usize, usize) -> bool {
+        let mut graph = DirectedGraph::with_node_count(self.nodes.len());
+        for node in self.nodes.iter() {
+            for adjacent_node_id in node.adjacent_node_ids.iter() {
+                if predicate(node.meta.node_id, *adjacent_node_id) {
+                    graph.add_edge(node.meta.node_id, *adjacent_node_id);
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::DirectedGraph;
+
+    fn edges(graph: &DirectedGraph) -> Vec<(usize, usize)> {
+        graph.nodes.iter()
+            .flat_map(|node| {
+                node.adjacent_node_ids.iter().map(|to| (node.meta.node_id, *to)).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn cycle_graph() -> DirectedGraph {
+        // 1 -> 2 -> 3 -> 1, 3 -> 4, 4 -> 4
+        let mut graph = DirectedGraph::default();
+        for (from, to) in [(1, 2), (2, 3), (3, 1), (3, 4), (4, 4)] {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    #[test]
+    fn removes_edges() {
+        let mut graph = cycle_graph();
+        graph.add_edge(1, 2);
+
+        assert!(graph.remove_edge(1, 2));
+        assert!(graph.remove_edge(3, 1));
+        assert!(!graph.remove_edge(3, 1));
+        assert!(!graph.remove_edge(7, 1));
+        assert_eq!(edges(&graph), vec![(1, 2), (2, 3), (3, 4), (4, 4)]);
+        assert_eq!(graph.strongly_connected_components().count(), 4);
+        assert!(graph.remove_edge(4, 4));
+        assert_eq!(graph.topological_order(), Ok(vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn removes_vertex_and_compacts_ids() {
+        let mut graph = cycle_graph();
+        graph.remove_vertex(2);
+
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(edges(&graph), vec![(2, 1), (2, 3), (3, 3)]);
+        assert_eq!(edges(&graph.reversed()), vec![(1, 2), (3, 2), (3, 3)]);
+        assert_eq!(graph.strongly_connected_components().count(), 3);
+        graph.remove_vertex(3);
+        assert_eq!(graph.topological_order(), Ok(vec![2, 1]));
+    }
+
+    #[test]
+    fn extracts_induced_subgraph() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/4.txt").unwrap()));
+        let subgraph = graph.induced_subgraph(&[6, 7, 8, 4, 7]);
+
+        assert_eq!(subgraph.original_ids, vec![6, 7, 8, 4]);
+        assert_eq!(subgraph.original_id(4), 4);
+        for (from, to) in edges(&subgraph.graph) {
+            assert!(graph.nodes[subgraph.original_id(from) - 1]
+                .adjacent_node_ids.contains(&subgraph.original_id(to)));
+        }
+        assert_eq!(subgraph.graph.strongly_connected_components().count(), 1);
+    }
+
+    #[test]
+    fn filters_edges() {
+        let graph = cycle_graph();
+        let forward = graph.filter_edges(|from, to| from < to);
+
+        assert_eq!(forward.node_count(), 4);
+        assert_eq!(edges(&forward), vec![(1, 2), (2, 3), (3, 4)]);
+        assert_eq!(forward.topological_order(), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(graph.filter_edges(|_, _| false).strongly_connected_components().count(), 4);
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    error::Error,
+    fmt::{Debug, Display, Formatter},
+};
+
+use crate::DirectedGraph;
+
+/// Graph has no topological order, `nodes` is one cycle in traversal order:
+/// every node has an edge to the next one and the last node has an edge to the first one.
+/// Nodes are ids, or labels for [`crate::labelled::LabelledGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T = usize> {
+    pub nodes: Vec<T>,
+}
+
+impl<T: Display> Display for Cycle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph contains a cycle: ")?;
+        for node_id in &self.nodes {
+            write!(f, "{} -> ", node_id)?;
+        }
+        write!(f, "{}", self.nodes[0])
+    }
+}
+
+impl<T: Debug + Display> Error for Cycle<T> {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Colour {
+    Unvisited,
+    OnStack,
+    Finished,
+}
+
+impl DirectedGraph {
+    /// Topological order computed by depth-first search: every edge goes from an earlier node
+    /// to a later one. Fails with one concrete cycle if the graph is not acyclic.
+    pub fn topological_order(&self) -> Result<Vec<usize>, Cycle> {
+        depth_first_order(self.nodes.len(), |node_id, index| self.adjacent_at(node_id, index))
+    }
+
+    /// Kahn's algorithm that always takes the smallest available node id,
+    /// which gives the lexicographically smallest topological order
+    pub fn topological_order_lexicographic(&self) -> Result<Vec<usize>, Cycle> {
+        lexicographic_order(self.nodes.len(), |node_id, index| self.adjacent_at(node_id, index))
+    }
+
+    fn adjacent_at(&self, node_id: usize, index: usize) -> Option<usize> {
+        self.nodes[node_id - 1].adjacent_node_ids.get(index).copied()
+    }
+}
+
+// `adjacent_at(node_id, index)` is the index-th adjacent node id of node_id,
+// which lets both graph representations share the traversal
+pub(crate) fn depth_first_order<F>(node_count: usize, adjacent_at: F) -> Result<Vec<usize>, Cycle>
+    where F: Fn(usize, usize) -> Option<usize> {
+    let mut colours = vec![Colour::Unvisited; node_count];
+    let mut finished = Vec::with_capacity(node_count);
+    // (node id, index of the next adjacent node to check)
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+
+    for root in 1..=node_count {
+        if colours[root - 1] != Colour::Unvisited {
+            continue;
+        }
+        colours[root - 1] = Colour::OnStack;
+        stack.push((root, 0));
+
+        while let Some((node_id, next)) = stack.last_mut() {
+            match adjacent_at(*node_id, *next) {
+                Some(adjacent_node_id) => {
+                    *next += 1;
+                    match colours[adjacent_node_id - 1] {
+                        Colour::Unvisited => {
+                            colours[adjacent_node_id - 1] = Colour::OnStack;
+                            stack.push((adjacent_node_id, 0));
+                        }
+                        Colour::OnStack => {
+                            // back edge, the cycle is the tail of the stack
+                            let start = stack.iter()
+                                .position(|(id, _)| *id == adjacent_node_id)
+                                .unwrap();
+                            let nodes = stack[start..].iter().map(|(id, _)| *id).collect();
+                            return Err(Cycle { nodes });
+                        }
+                        Colour::Finished => {}
+                    }
+                }
+                None => {
+                    colours[*node_id - 1] = Colour::Finished;
+                    finished.push(*node_id);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    finished.reverse();
+    Ok(finished)
+}
+
+pub(crate) fn lexicographic_order<F>(node_count: usize, adjacent_at: F) -> Result<Vec<usize>, Cycle>
+    where F: Fn(usize, usize) -> Option<usize> {
+    let adjacent_at = &adjacent_at;
+    let adjacent = |node_id: usize| (0..).map_while(move |index| adjacent_at(node_id, index));
+    let mut in_degrees = vec![0_usize; node_count];
+    for node_id in 1..=node_count {
+        for adjacent_node_id in adjacent(node_id) {
+            in_degrees[adjacent_node_id - 1] += 1;
+        }
+    }
+
+    let mut available: BinaryHeap<Reverse<usize>> = (1..=node_count)
+        .filter(|node_id| in_degrees[node_id - 1] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(node_count);
+    while let Some(Reverse(node_id)) = available.pop() {
+        order.push(node_id);
+        for adjacent_node_id in adjacent(node_id) {
+            in_degrees[adjacent_node_id - 1] -= 1;
+            if in_degrees[adjacent_node_id - 1] == 0 {
+                available.push(Reverse(adjacent_node_id));
+            }
+        }
+    }
+
+    if order.len() < node_count {
+        // nodes left behind are on or after a cycle, let depth-first search find one
+        return Err(depth_first_order(node_count, adjacent_at).unwrap_err());
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::DirectedGraph;
+
+    fn assert_is_topological(graph: &DirectedGraph, order: &[usize]) {
+        assert_eq!(order.len(), graph.node_count());
+        let mut position = vec![0; order.len()];
+        for (index, node_id) in order.iter().enumerate() {
+            position[node_id - 1] = index;
+        }
+        for node in graph.nodes.iter() {
+            for to in &node.adjacent_node_ids {
+                assert!(position[node.meta.node_id - 1] < position[to - 1]);
+            }
+        }
+    }
+
+    fn assert_is_cycle(graph: &DirectedGraph, cycle: &[usize]) {
+        assert!(!cycle.is_empty());
+        for (index, from) in cycle.iter().enumerate() {
+            let to = cycle[(index + 1) % cycle.len()];
+            assert!(graph.nodes[from - 1].adjacent_node_ids.contains(&to));
+        }
+    }
+
+    #[test]
+    fn orders_acyclic_graph() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(5, 1);
+        graph.add_edge(1, 3);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(5, 4);
+
+        assert_is_topological(&graph, &graph.topological_order().unwrap());
+        assert_eq!(graph.topological_order_lexicographic().unwrap(), vec![2, 5, 1, 3, 4]);
+    }
+
+    #[test]
+    fn reports_cycle() {
+        let graph = DirectedGraph::from(utils::read_matrix(File::open("./test_data/1.txt").unwrap()));
+
+        let cycle = graph.topological_order().unwrap_err();
+        assert_is_cycle(&graph, &cycle.nodes);
+        let cycle = graph.topological_order_lexicographic().unwrap_err();
+        assert_is_cycle(&graph, &cycle.nodes);
+    }
+
+    #[test]
+    fn reports_self_loop() {
+        let mut graph = DirectedGraph::default();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 2);
+
+        assert_eq!(graph.topological_order().unwrap_err().nodes, vec![2]);
+        assert_eq!(graph.topological_order_lexicographic().unwrap_err().to_string(),
+                   "graph contains a cycle: 2 -> 2");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::DirectedGraph;
+
+/// 2-SAT instance: conjunction of clauses with two literals each.
+/// Variables are numbered from 1, a negative literal is the negation of its variable.
+#[derive(Debug, Default, Clone)]
+pub struct TwoSat {
+    variable_count: usize,
+    clauses: Vec<(i64, i64)>,
+}
+
+impl TwoSat {
+    /// For init from assignment file: a line with the number of variables,
+    /// then a line per clause with two literals separated by a space
+    pub fn from_text(text: String) -> Self {
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+        let variable_count = lines.next()
+            .expect("Missing variable count")
+            .trim()
+            .parse::<usize>()
+            .expect("Parsing error");
+        let mut two_sat = TwoSat::new(variable_count);
+        for line in lines {
+            let literals = line.split_whitespace()
+                .map(|literal| literal.parse::<i64>().expect("Parsing error"))
+                .collect::<Vec<i64>>();
+            assert_eq!(literals.len(), 2, "Clause should have two literals");
+            two_sat.add_clause(literals[0], literals[1]);
+        }
+        two_sat
+    }
+
+    pub fn new(variable_count: usize) -> Self {
+        TwoSat { variable_count, clauses: Vec::new() }
+    }
+
+    /// Adds clause `a OR b`
+    pub fn add_clause(&mut self, a: i64, b: i64) {
+        assert!(a != 0 && b != 0, "Literals start from 1");
+        assert!(a.unsigned_abs() as usize <= self.variable_count, "Unknown variable {}", a);
+        assert!(b.unsigned_abs() as usize <= self.variable_count, "Unknown variable {}", b);
+        self.clauses.push((a, b));
+    }
+
+    /// Graph on 2n literals: node `x` is the variable `x`, node `n + x` is its negation.
+    /// Clause `a OR b` gives the implications `NOT a -> b` and `NOT b -> a`.
+    pub fn implication_graph(&self) -> DirectedGraph {
+        let mut graph = DirectedGraph::with_node_count(2 * self.variable_count);
+        for (a, b) in self.clauses.iter() {
+            graph.add_edge(self.literal_node(-a), self.literal_node(*b));
+            graph.add_edge(self.literal_node(-b), self.literal_node(*a));
+        }
+        graph
+    }
+
+    pub fn is_satisfiable(&self) -> bool {
+        self.solve().is_some()
+    }
+
+    /// Satisfying assignment indexed by variable - 1, or None if the formula is unsatisfiable
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let components = self.implication_graph().strongly_connected_components();
+        (1..=self.variable_count)
+            .map(|variable| {
+                let positive = components.component_of(variable);
+                let negative = components.component_of(self.variable_count + variable);
+                // components are numbered in reverse topological order, so the literal
+                // with the smaller id can't imply its negation
+                match positive.cmp(&negative) {
+                    std::cmp::Ordering::Equal => None,
+                    ordering => Some(ordering.is_lt()),
+                }
+            })
+            .collect()
+    }
+
+    fn literal_node(&self, literal: i64) -> usize {
+        let variable = literal.unsigned_abs() as usize;
+        if literal > 0 { variable } else { self.variable_count + variable }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use crate::two_sat::TwoSat;
+
+    fn read(path: &str) -> TwoSat {
+        TwoSat::from_text(utils::read_as_string(File::open(path).expect(utils::FILE_NOT_FOUND_MSG)))
+    }
+
+    fn assert_satisfies(two_sat: &TwoSat, assignment: &[bool]) {
+        assert_eq!(assignment.len(), two_sat.variable_count);
+        let value = |literal: i64| assignment[literal.unsigned_abs() as usize - 1] == (literal > 0);
+        for (a, b) in two_sat.clauses.iter() {
+            assert!(value(*a) || value(*b), "clause {} {} is not satisfied", a, b);
+        }
+    }
+
+    #[test]
+    fn satisfiable() {
+        let two_sat = read("./test_data/2sat_1.txt");
+        assert_satisfies(&two_sat, &two_sat.solve().unwrap());
+    }
+
+    #[test]
+    fn unsatisfiable() {
+        assert!(!read("./test_data/2sat_2.txt").is_satisfiable());
+    }
+
+    #[test]
+    fn unit_clauses_force_values() {
+        let two_sat = read("./test_data/2sat_3.txt");
+        let assignment = two_sat.solve().unwrap();
+        assert_satisfies(&two_sat, &assignment);
+        assert_eq!(assignment, vec![true, false, true]);
+    }
+}
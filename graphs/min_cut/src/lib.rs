@@ -0,0 +1,259 @@
+use graph_core::{Components, union_find::UnionFind};
+use utils::XorShift64;
+
+// graphs up to this many nodes are cut by trying every partition
+const BRUTE_FORCE_NODE_COUNT: usize = 6;
+
+/// Undirected multigraph with nodes numbered from 1, every edge stored once
+#[derive(Debug, Clone, Default)]
+pub struct MultiGraph {
+    node_count: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+/// Cut with its two sides, both ascending; `left` holds node 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// number of edges crossing the cut
+    pub size: usize,
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+}
+
+impl MultiGraph {
+    /// Adjacency lists, one line per node: its id followed by its adjacent node ids,
+    /// separated by tabs or spaces. Every edge is listed at both ends.
+    pub fn from_text(text: String) -> Self {
+        let mut graph = MultiGraph::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let ids = line.split_whitespace()
+                .map(|id| id.parse::<usize>().expect("Parsing error"))
+                .collect::<Vec<usize>>();
+            let node_id = ids[0];
+            graph.node_count = graph.node_count.max(node_id);
+            for adjacent_node_id in ids[1..].iter().copied() {
+                graph.node_count = graph.node_count.max(adjacent_node_id);
+                // keep the copy listed at the smaller end
+                if node_id < adjacent_node_id {
+                    graph.edges.push((node_id, adjacent_node_id));
+                }
+            }
+        }
+        graph
+    }
+
+    /// Self-loops can never cross a cut and are dropped
+    pub fn from_edges(node_count: usize, edges: &[(usize, usize)]) -> Self {
+        assert!(edges.iter().all(|(from, to)| *from > 0 && *to > 0), "Node ids start from 1");
+        assert!(edges.iter().all(|(from, to)| *from <= node_count && *to <= node_count), "Unknown node id");
+        let edges = edges.iter().copied().filter(|(from, to)| from != to).collect();
+        MultiGraph { node_count, edges }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Karger's random contraction repeated [`karger_repetitions`] times,
+    /// wrong with probability at most 1/n. None for graphs with fewer than two nodes.
+    pub fn karger(&self, seed: u64) -> Option<MinCut> {
+        self.karger_with_repetitions(seed, karger_repetitions(self.node_count))
+    }
+
+    pub fn karger_with_repetitions(&self, seed: u64, repetitions: usize) -> Option<MinCut> {
+        let mut rng = XorShift64::new(seed);
+        self.best_of(repetitions, || {
+            let components = contract(self.node_count, &self.edges, 2, &mut rng);
+            (1..=self.node_count).map(|node_id| components.component_of(node_id) == 1).collect()
+        })
+    }
+
+    /// Karger-Stein recursive contraction repeated [`karger_stein_repetitions`] times:
+    /// a run costs more than one contraction, but succeeds with probability
+    /// about 1 / log n instead of 2 / n^2, so far fewer runs are needed
+    pub fn karger_stein(&self, seed: u64) -> Option<MinCut> {
+        self.karger_stein_with_repetitions(seed, karger_stein_repetitions(self.node_count))
+    }
+
+    pub fn karger_stein_with_repetitions(&self, seed: u64, repetitions: usize) -> Option<MinCut> {
+        let mut rng = XorShift64::new(seed);
+        self.best_of(repetitions, || recursive_contraction(self.node_count, &self.edges, &mut rng).1)
+    }
+
+    // smallest of the cuts given by `trial` as the side of every node
+    fn best_of<T>(&self, repetitions: usize, mut trial: T) -> Option<MinCut>
+        where T: FnMut() -> Vec<bool> {
+        if self.node_count < 2 {
+            return None;
+        }
+        let mut best: Option<MinCut> = None;
+        for _ in 0..repetitions.max(1) {
+            let cut = self.cut(&trial());
+            if best.as_ref().is_none_or(|best| cut.size < best.size) {
+                best = Some(cut);
+            }
+            if best.as_ref().is_some_and(|best| best.size == 0) {
+                break;
+            }
+        }
+        best
+    }
+
+    // `sides` tells for every node, indexed by node_id - 1, whether it is on node 1's side
+    fn cut(&self, sides: &[bool]) -> MinCut {
+        let size = crossing_edges(&self.edges, sides);
+        let (left, right) = (1..=self.node_count).partition(|node_id| sides[node_id - 1] == sides[0]);
+        MinCut { size, left, right }
+    }
+}
+
+/// n^2 ln n rounds of random contraction
+pub fn karger_repetitions(node_count: usize) -> usize {
+    let n = node_count as f64;
+    (n * n * n.ln()).ceil().max(1.0) as usize
+}
+
+/// log2(n)^2 rounds of recursive contraction
+pub fn karger_stein_repetitions(node_count: usize) -> usize {
+    (node_count as f64).log2().powi(2).ceil().max(1.0) as usize
+}
+
+// contracting edges in a random order and skipping those inside a supernode is the same
+// as contracting a uniformly chosen remaining edge every time; supernodes are numbered
+// by their smallest node, so node 1 is always in supernode 1
+fn contract(node_count: usize, edges: &[(usize, usize)], target: usize, rng: &mut XorShift64) -> Components {
+    let mut order = edges.to_vec();
+    for index in (1..order.len()).rev() {
+        order.swap(index, rng.below(index + 1));
+    }
+    let mut supernodes = UnionFind::new(node_count);
+    for (from, to) in order {
+        if supernodes.set_count() <= target {
+            break;
+        }
+        supernodes.union(from, to);
+    }
+    supernodes.components()
+}
+
+// contracts to n / sqrt(2) supernodes twice and recurses into both,
+// returns the best cut size with the side of every node
+fn recursive_contraction(node_count: usize, edges: &[(usize, usize)], rng: &mut XorShift64) -> (usize, Vec<bool>) {
+    if edges.is_empty() {
+        return (0, (1..=node_count).map(|node_id| node_id == 1).collect());
+    }
+    if node_count <= BRUTE_FORCE_NODE_COUNT {
+        return brute_force(node_count, edges);
+    }
+    let target = (1.0 + node_count as f64 / std::f64::consts::SQRT_2).ceil() as usize;
+    let mut best = (usize::MAX, Vec::new());
+    for _ in 0..2 {
+        let supernodes = contract(node_count, edges, target, rng);
+        let contracted_edges = edges.iter()
+            .map(|(from, to)| (supernodes.component_of(*from), supernodes.component_of(*to)))
+            .filter(|(from, to)| from != to)
+            .collect::<Vec<(usize, usize)>>();
+        let (size, sides) = recursive_contraction(supernodes.count(), &contracted_edges, rng);
+        if size < best.0 {
+            let sides = (1..=node_count).map(|node_id| sides[supernodes.component_of(node_id) - 1]).collect();
+            best = (size, sides);
+        }
+    }
+    best
+}
+
+// every partition with node 1 on the left and a non-empty right side
+fn brute_force(node_count: usize, edges: &[(usize, usize)]) -> (usize, Vec<bool>) {
+    (1..1_usize << (node_count - 1))
+        .map(|mask| {
+            let sides = (1..=node_count)
+                .map(|node_id| node_id == 1 || mask & (1 << (node_id - 2)) == 0)
+                .collect::<Vec<bool>>();
+            (crossing_edges(edges, &sides), sides)
+        })
+        .min_by_key(|(size, _)| *size)
+        .unwrap()
+}
+
+fn crossing_edges(edges: &[(usize, usize)], sides: &[bool]) -> usize {
+    edges.iter().filter(|(from, to)| sides[from - 1] != sides[to - 1]).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use utils::XorShift64;
+
+    use crate::{brute_force, MinCut, MultiGraph};
+
+    fn test_graph() -> MultiGraph {
+        MultiGraph::from_text(utils::read_as_string(File::open("./test_data/1.txt").unwrap()))
+    }
+
+    fn assert_consistent(graph: &MultiGraph, cut: &MinCut) {
+        let crossing = graph.edges().iter()
+            .filter(|(from, to)| cut.left.contains(from) != cut.left.contains(to))
+            .count();
+        assert_eq!(crossing, cut.size);
+        assert_eq!(cut.left.len() + cut.right.len(), graph.node_count());
+        assert_eq!(cut.left[0], 1);
+        assert!(!cut.right.is_empty());
+    }
+
+    #[test]
+    fn parses_adjacency_lists() {
+        let graph = test_graph();
+
+        assert_eq!(graph.node_count(), 8);
+        assert_eq!(graph.edges().len(), 14);
+    }
+
+    #[test]
+    fn finds_min_cut_of_two_clusters() {
+        let graph = test_graph();
+        let expected = MinCut { size: 2, left: vec![1, 2, 3, 4], right: vec![5, 6, 7, 8] };
+
+        assert_eq!(graph.karger(1), Some(expected.clone()));
+        assert_eq!(graph.karger_stein(1), Some(expected));
+    }
+
+    #[test]
+    fn same_seed_same_cut() {
+        let graph = test_graph();
+
+        assert_eq!(graph.karger_with_repetitions(5, 3), graph.karger_with_repetitions(5, 3));
+        assert_eq!(graph.karger_stein_with_repetitions(5, 1), graph.karger_stein_with_repetitions(5, 1));
+    }
+
+    #[test]
+    fn disconnected_and_tiny_graphs() {
+        let graph = MultiGraph::from_edges(5, &[(1, 2), (2, 3), (4, 5)]);
+
+        assert_eq!(graph.karger(3).unwrap().size, 0);
+        assert_eq!(graph.karger_stein(3).unwrap().size, 0);
+        assert_eq!(MultiGraph::from_edges(1, &[(1, 1)]).karger(3), None);
+    }
+
+    #[test]
+    fn agrees_with_brute_force() {
+        let mut rng = XorShift64::new(19);
+        for seed in 0..40 {
+            let node_count = 2 + rng.below(10);
+            let edges = (0..node_count + rng.below(3 * node_count))
+                .map(|_| (1 + rng.below(node_count), 1 + rng.below(node_count)))
+                .collect::<Vec<(usize, usize)>>();
+            let graph = MultiGraph::from_edges(node_count, &edges);
+            let expected = brute_force(node_count, graph.edges()).0;
+
+            for cut in [graph.karger(seed).unwrap(), graph.karger_stein(seed).unwrap()] {
+                assert_consistent(&graph, &cut);
+                assert_eq!(cut.size, expected);
+            }
+        }
+    }
+}
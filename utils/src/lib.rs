@@ -1,7 +1,6 @@
 use std::fs::File;
 use std::io::{self, BufReader};
 use std::io::Read;
-use std::usize;
 
 pub const FILE_NOT_FOUND_MSG: &str = "Can't find a file";
 
@@ -33,3 +32,56 @@ pub fn read_matrix(file: File) -> Vec<Vec<usize>> {
         })
         .collect::<Vec<Vec<usize>>>()
 }
+
+/// Small seedable xorshift generator, so randomized algorithms and tests are reproducible
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    pub fn new(seed: u64) -> Self {
+        // zero is a fixed point of xorshift
+        XorShift64 { state: seed ^ 0x9E37_79B9_7F4A_7C15 }.warmed_up()
+    }
+
+    fn warmed_up(mut self) -> Self {
+        if self.state == 0 {
+            self.state = 1;
+        }
+        self.next_u64();
+        self
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Uniform number from 0 to bound - 1
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Bound should be positive");
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::XorShift64;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = XorShift64::new(42);
+        let mut b = XorShift64::new(42);
+        let mut c = XorShift64::new(0);
+
+        for _ in 0..100 {
+            let value = a.below(10);
+            assert_eq!(value, b.below(10));
+            assert!(value < 10);
+            assert_ne!(c.next_u64(), 0);
+        }
+    }
+}